@@ -0,0 +1,6 @@
+-- Add migration script here
+
+/* argon2 PHC string. Users created before authentication existed,
+   or through `createUser`, have no password and cannot log in.
+*/
+alter table user_ add column if not exists password_hash text;
@@ -0,0 +1,13 @@
+-- Add migration script here
+
+/* There is no API to grant roles yet.
+   Promote a user with `insert into user_role (user_id, role) values ('<id>', 'admin')`.
+*/
+create table if not exists user_role (
+   user_id UUID not null references user_ (id) on delete cascade,
+   role text not null check (role in ('admin')),
+
+   created_at timestamp with time zone not null default now(),
+
+   primary key (user_id, role)
+);
@@ -0,0 +1,2 @@
+create index if not exists user_created_at_id_idx on user_ (created_at, id);
+create index if not exists user_updated_at_id_idx on user_ (updated_at, id);
@@ -0,0 +1,11 @@
+-- Add migration script here
+
+/* Automatic persisted queries, used when `GRAPHQL_APQ_STORE=postgres`.
+   In persisted-only mode, queries can only be added here, e.g: from a deploy step.
+*/
+create table if not exists persisted_query (
+   hash text primary key,
+   query text not null,
+
+   created_at timestamp with time zone not null default now()
+);
@@ -0,0 +1,44 @@
+mod postgres;
+
+use std::sync::Arc;
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
+    ErrorExtensions, Request, ServerError, ServerResult,
+};
+pub use postgres::PostgresStorage;
+
+use crate::Error;
+
+/// Rejects requests sending a query document.
+/// Only the hashes of already persisted queries are executed.
+/// Must be registered before `ApolloPersistedQueries`, which resolves the hashes.
+pub struct PersistedQueriesOnly;
+
+impl ExtensionFactory for PersistedQueriesOnly {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedQueriesOnlyExtension)
+    }
+}
+
+struct PersistedQueriesOnlyExtension;
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for PersistedQueriesOnlyExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        if !request.query.is_empty() {
+            let error =
+                Error::InvalidArgument(String::from("only persisted queries are allowed")).extend();
+            let mut server_error = ServerError::new(error.message, None);
+            server_error.extensions = error.extensions;
+            return Err(server_error);
+        }
+
+        next.run(ctx, request).await
+    }
+}
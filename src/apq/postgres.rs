@@ -0,0 +1,59 @@
+use async_graphql::extensions::apollo_persisted_queries::CacheStorage;
+use sha2::{Digest, Sha256};
+
+use crate::db::DB;
+
+/// Persisted queries stored in the `persisted_query` table.
+/// They survive restarts, and are shared between instances.
+/// A query is only stored under its own sha256 hash, a row is never replaced.
+#[derive(Clone)]
+pub struct PostgresStorage {
+    db: DB,
+}
+
+impl PostgresStorage {
+    pub fn new(db: DB) -> Self {
+        Self { db }
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl CacheStorage for PostgresStorage {
+    async fn get(&self, key: String) -> Option<String> {
+        const QUERY: &str = "select query from persisted_query where hash = $1";
+
+        match sqlx::query_scalar::<_, String>(QUERY)
+            .bind(key)
+            .fetch_optional(&self.db)
+            .await
+        {
+            Err(err) => {
+                log::error!("apq: finding persisted query: {}", &err);
+                None
+            }
+            Ok(res) => res,
+        }
+    }
+
+    async fn set(&self, key: String, query: String) {
+        // Otherwise any client could store a query under the hash of another one,
+        // and every instance would serve it for good
+        let hash = format!("{:x}", Sha256::digest(query.as_bytes()));
+        if hash != key {
+            log::warn!("apq: refusing to persist a query under a hash that isn't its own");
+            return;
+        }
+
+        const QUERY: &str =
+            "insert into persisted_query (hash, query) values ($1, $2) on conflict do nothing";
+
+        if let Err(err) = sqlx::query(QUERY)
+            .bind(key)
+            .bind(query)
+            .execute(&self.db)
+            .await
+        {
+            log::error!("apq: persisting query: {}", &err);
+        }
+    }
+}
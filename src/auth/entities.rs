@@ -0,0 +1,27 @@
+use uuid::Uuid;
+
+use crate::user::entities::{Role, User};
+
+/// The authenticated caller of a request.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub user_id: Uuid,
+    pub name: String,
+    pub roles: Vec<Role>,
+}
+
+impl Principal {
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
+#[derive(Debug)]
+pub struct AuthToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    /// Access token lifetime, in seconds
+    pub expires_in: i64,
+    pub user: User,
+}
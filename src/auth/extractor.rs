@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use async_graphql::{Data, ErrorExtensions};
+use axum::{
+    async_trait,
+    extract::{Extension, FromRequest, RequestParts},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+use super::Principal;
+use crate::context::ServerContext;
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// Keys of the `Authorization` value in the `connection_init` payload of WebSockets
+const CONNECTION_INIT_KEYS: &[&str] = &["Authorization", "authorization"];
+
+/// Extracts the principal from the `Authorization: Bearer <token>` header.
+///
+/// Requests without the header are anonymous, while requests with an invalid
+/// or expired token are rejected with `401 Unauthorized`.
+#[derive(Debug, Clone)]
+pub struct Auth(pub Option<Principal>);
+
+#[async_trait]
+impl<B> FromRequest<B> for Auth
+where
+    B: Send,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let authorization = match req.headers().get(header::AUTHORIZATION) {
+            None => return Ok(Self(None)),
+            Some(value) => value
+                .to_str()
+                .map(String::from)
+                .map_err(|_| unauthorized(invalid_authorization()))?,
+        };
+
+        let Extension(server_ctx) = Extension::<Arc<ServerContext>>::from_request(req)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        match authenticate(&server_ctx, &authorization) {
+            Ok(principal) => Ok(Self(Some(principal))),
+            Err(err) => Err(unauthorized(err)),
+        }
+    }
+}
+
+/// Authenticates an `Authorization` value, i.e: `Bearer <token>`
+fn authenticate(
+    server_ctx: &ServerContext,
+    authorization: &str,
+) -> Result<Principal, crate::Error> {
+    let token = authorization
+        .strip_prefix(BEARER_PREFIX)
+        .ok_or_else(invalid_authorization)?;
+    server_ctx.auth_service.authenticate(token.trim())
+}
+
+/// The data of a WebSocket, from the `Authorization` of its `connection_init` payload, e.g:
+/// `{ "Authorization": "Bearer <token>" }`. Like HTTP requests, WebSockets without it are
+/// anonymous, while an invalid or expired token closes the WebSocket.
+pub fn connection_init_data(
+    server_ctx: &ServerContext,
+    payload: &serde_json::Value,
+) -> async_graphql::Result<Data> {
+    let mut data = Data::default();
+    let authorization = CONNECTION_INIT_KEYS
+        .iter()
+        .find_map(|key| payload.get(key))
+        .map(|value| value.as_str().ok_or_else(invalid_authorization))
+        .transpose()
+        .map_err(|err| err.extend())?;
+
+    if let Some(authorization) = authorization {
+        let principal = authenticate(server_ctx, authorization).map_err(|err| err.extend())?;
+        data.insert(principal);
+    }
+    Ok(data)
+}
+
+fn invalid_authorization() -> crate::Error {
+    crate::Error::Unauthenticated(String::from("invalid authorization header"))
+}
+
+/// A GraphQL shaped error response, so clients can handle it like any other error
+fn unauthorized(err: crate::Error) -> Response {
+    let body = json!({
+        "data": null,
+        "errors": [{
+            "message": err.to_string(),
+            "extensions": { "code": err.code() },
+        }],
+    });
+    (StatusCode::UNAUTHORIZED, Json(body)).into_response()
+}
@@ -0,0 +1,66 @@
+use std::borrow::Borrow;
+
+use async_graphql::{Context, ErrorExtensions, Guard, Result, ID};
+use uuid::Uuid;
+
+use super::Principal;
+use crate::{
+    errors::core::Error,
+    user::{entities::Role, model::user_id_of},
+};
+
+/// Allows the request if the principal has the given role
+pub struct RoleGuard {
+    role: Role,
+}
+
+impl RoleGuard {
+    pub fn new(role: Role) -> Self {
+        Self { role }
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Guard for RoleGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        let principal = principal(ctx)?;
+        if principal.has_role(self.role) {
+            Ok(())
+        } else {
+            Err(crate::Error::from(Error::PermissionDenied).extend())
+        }
+    }
+}
+
+/// Allows the request if the principal is the owner of the resource, or an admin
+pub struct OwnerOrAdminGuard {
+    /// The error of the arguments if they don't name a user
+    owner_id: std::result::Result<Uuid, crate::Error>,
+}
+
+impl OwnerOrAdminGuard {
+    /// The owner is the user given either by its id or its global id, see `user_id_of`
+    pub fn new(id: impl Borrow<Option<Uuid>>, node_id: impl Borrow<Option<ID>>) -> Self {
+        Self {
+            owner_id: user_id_of(*id.borrow(), node_id.borrow().as_ref()).map_err(Into::into),
+        }
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Guard for OwnerOrAdminGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        let principal = principal(ctx)?;
+        let owner_id = self.owner_id.clone().map_err(|err| err.extend())?;
+        if principal.user_id == owner_id || principal.has_role(Role::Admin) {
+            Ok(())
+        } else {
+            Err(crate::Error::from(Error::PermissionDenied).extend())
+        }
+    }
+}
+
+fn principal<'a>(ctx: &'a Context<'_>) -> Result<&'a Principal> {
+    ctx.data_opt::<Principal>()
+        .ok_or_else(|| crate::Error::from(Error::AuthenticationRequired).extend())
+}
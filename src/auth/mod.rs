@@ -0,0 +1,12 @@
+mod entities;
+pub mod extractor;
+pub mod guard;
+mod model;
+mod password;
+mod service;
+mod token;
+
+// public
+pub mod resolver;
+pub use entities::Principal;
+pub use service::Service;
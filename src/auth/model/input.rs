@@ -0,0 +1,22 @@
+use async_graphql::InputObject;
+
+#[derive(InputObject)]
+pub struct RegisterInput {
+    pub name: String,
+    pub full_name: Option<String>,
+    #[graphql(secret)]
+    pub password: String,
+}
+
+#[derive(InputObject)]
+pub struct LoginInput {
+    pub name: String,
+    #[graphql(secret)]
+    pub password: String,
+}
+
+#[derive(InputObject)]
+pub struct RefreshTokenInput {
+    #[graphql(secret)]
+    pub refresh_token: String,
+}
@@ -0,0 +1,27 @@
+pub mod input;
+
+use async_graphql::SimpleObject;
+
+use crate::{auth::entities, user::model::User};
+
+#[derive(Debug, SimpleObject)]
+pub struct AuthPayload {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    // Access token lifetime, in seconds.
+    pub expires_in: i64,
+    pub user: User,
+}
+
+impl From<entities::AuthToken> for AuthPayload {
+    fn from(token: entities::AuthToken) -> Self {
+        Self {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            token_type: token.token_type,
+            expires_in: token.expires_in,
+            user: token.user.into(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+
+use crate::errors::core::Error;
+
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Hash of a password nobody has, with the parameters of `hash`, see `verify_dummy`
+const DUMMY_HASH: &str =
+    "$argon2id$v=19$m=4096,t=3,p=1$bmFobGEtZHVtbXktc2FsdA$roL5ekko9+kb14gH3w8DfRd17uj2ThipbJdR6F2ZZAw";
+
+/// Hash a plain text password into an argon2 PHC string
+pub fn hash(password: &str) -> Result<String, Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default().hash_password(password.as_bytes(), &salt)?;
+    Ok(hash.to_string())
+}
+
+/// returns true if the password matches the argon2 PHC string. false otherwise
+pub fn verify(password: &str, password_hash: &str) -> Result<bool, Error> {
+    let parsed_hash = PasswordHash::new(password_hash)?;
+    Ok(Argon2::default()
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .is_ok())
+}
+
+/// Takes as long as `verify`, for when there is no hash to verify against, e.g: an unknown username.
+/// Otherwise the response time tells whether the user exists.
+pub fn verify_dummy(password: &str) {
+    let _ = verify(password, DUMMY_HASH);
+}
+
+#[cfg(test)]
+mod tests {
+    use argon2::{password_hash, Algorithm, Params};
+
+    use super::*;
+
+    #[test]
+    fn dummy_hash_params() -> Result<(), password_hash::Error> {
+        let dummy = PasswordHash::new(DUMMY_HASH)?;
+        assert_eq!(dummy.algorithm, Algorithm::default().ident());
+        let params = Params::try_from(&dummy)?;
+        let default = Params::default();
+        assert_eq!(
+            (params.m_cost(), params.t_cost(), params.p_cost()),
+            (default.m_cost(), default.t_cost(), default.p_cost())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, FieldResult, Object};
+
+use super::{
+    model::{input, AuthPayload},
+    Principal,
+};
+use crate::{
+    context::ServerContext, errors::core::Error::AuthenticationRequired, user::model::User,
+};
+
+#[derive(Default)]
+pub struct AuthQuery;
+
+#[derive(Default)]
+pub struct AuthMutation;
+
+#[Object]
+impl AuthQuery {
+    /// The currently authenticated user
+    pub async fn me(&self, ctx: &Context<'_>) -> FieldResult<User> {
+        let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+        let principal = match ctx.data_opt::<Principal>() {
+            Some(principal) => principal,
+            None => {
+                let err: crate::Error = AuthenticationRequired.into();
+                return Err(err.extend());
+            }
+        };
+
+        let result = server_ctx.user_service.find_user(principal.user_id).await;
+        match result {
+            Ok(res) => Ok(res.into()),
+            Err(err) => Err(err.extend()),
+        }
+    }
+}
+
+#[Object]
+impl AuthMutation {
+    pub async fn register(
+        &self,
+        ctx: &Context<'_>,
+        input: input::RegisterInput,
+    ) -> FieldResult<AuthPayload> {
+        let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+
+        let result = server_ctx.auth_service.register(input.into()).await;
+        match result {
+            Ok(res) => Ok(res.into()),
+            Err(err) => Err(err.extend()),
+        }
+    }
+    pub async fn login(
+        &self,
+        ctx: &Context<'_>,
+        input: input::LoginInput,
+    ) -> FieldResult<AuthPayload> {
+        let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+
+        let result = server_ctx.auth_service.login(input.into()).await;
+        match result {
+            Ok(res) => Ok(res.into()),
+            Err(err) => Err(err.extend()),
+        }
+    }
+    pub async fn refresh_token(
+        &self,
+        ctx: &Context<'_>,
+        input: input::RefreshTokenInput,
+    ) -> FieldResult<AuthPayload> {
+        let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+
+        let result = server_ctx
+            .auth_service
+            .refresh_token(&input.refresh_token)
+            .await;
+        match result {
+            Ok(res) => Ok(res.into()),
+            Err(err) => Err(err.extend()),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use super::Service;
+use crate::{
+    auth::{entities::Principal, token::TokenKind},
+    errors::Error,
+};
+
+impl Service {
+    /// Validate an access token and returns the principal it was issued to
+    pub fn authenticate(&self, access_token: &str) -> Result<Principal, Error> {
+        let principal = self.keys.decode(access_token, TokenKind::Access)?;
+
+        Ok(principal)
+    }
+}
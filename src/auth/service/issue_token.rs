@@ -0,0 +1,34 @@
+use super::Service;
+use crate::{
+    auth::{
+        entities::{AuthToken, Principal},
+        token::TokenKind,
+    },
+    errors::Error,
+    user::entities::User,
+};
+
+const TOKEN_TYPE: &str = "Bearer";
+
+impl Service {
+    /// Issue a new access and refresh token pair for the user
+    pub(super) async fn issue_token(&self, user: User) -> Result<AuthToken, Error> {
+        let roles = self.user_service.find_user_roles(user.id).await?;
+        let principal = Principal {
+            user_id: user.id,
+            name: user.name.clone(),
+            roles,
+        };
+
+        let access_token = self.keys.encode(&principal, TokenKind::Access)?;
+        let refresh_token = self.keys.encode(&principal, TokenKind::Refresh)?;
+
+        Ok(AuthToken {
+            access_token,
+            refresh_token,
+            token_type: TOKEN_TYPE.to_string(),
+            expires_in: self.keys.access_token_ttl,
+            user,
+        })
+    }
+}
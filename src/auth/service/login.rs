@@ -0,0 +1,33 @@
+use super::{LoginInput, Service};
+use crate::{
+    auth::{entities::AuthToken, password},
+    errors::{self, core::Error},
+};
+
+impl Service {
+    pub async fn login(&self, input: LoginInput) -> Result<AuthToken, errors::Error> {
+        // Don't tell the client whether the username or the password is wrong.
+        // Without a hash to verify, `verify_dummy` takes as long as a wrong password.
+        let user = match self.user_service.find_user_by_name(&input.name).await {
+            Ok(user) => user,
+            Err(errors::Error::NotFound(_)) => {
+                password::verify_dummy(&input.password);
+                return Err(Error::InvalidCredentials.into());
+            }
+            Err(err) => return Err(err),
+        };
+        let password_hash = match user.password_hash.as_deref() {
+            Some(password_hash) => password_hash,
+            None => {
+                password::verify_dummy(&input.password);
+                return Err(Error::InvalidCredentials.into());
+            }
+        };
+
+        if !password::verify(&input.password, password_hash)? {
+            return Err(Error::InvalidCredentials.into());
+        }
+
+        self.issue_token(user).await
+    }
+}
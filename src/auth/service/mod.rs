@@ -0,0 +1,55 @@
+mod authenticate;
+mod issue_token;
+mod login;
+mod refresh_token;
+mod register;
+
+use std::sync::Arc;
+
+use super::{model::input, token::Keys};
+use crate::{config, user};
+
+#[derive(Debug)]
+pub struct Service {
+    keys: Keys,
+    user_service: Arc<user::Service>,
+}
+
+impl Service {
+    pub fn new(jwt: &config::Jwt, user_service: Arc<user::Service>) -> Self {
+        let keys = Keys::new(jwt);
+        Self { keys, user_service }
+    }
+}
+
+#[derive(Debug)]
+pub struct RegisterInput {
+    pub name: String,
+    pub full_name: Option<String>,
+    pub password: String,
+}
+
+#[derive(Debug)]
+pub struct LoginInput {
+    pub name: String,
+    pub password: String,
+}
+
+impl From<input::RegisterInput> for RegisterInput {
+    fn from(input: input::RegisterInput) -> Self {
+        Self {
+            name: input.name,
+            full_name: input.full_name,
+            password: input.password,
+        }
+    }
+}
+
+impl From<input::LoginInput> for LoginInput {
+    fn from(input: input::LoginInput) -> Self {
+        Self {
+            name: input.name,
+            password: input.password,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use super::Service;
+use crate::{
+    auth::{entities::AuthToken, token::TokenKind},
+    errors::{self, core::Error},
+};
+
+impl Service {
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken, errors::Error> {
+        let principal = self.keys.decode(refresh_token, TokenKind::Refresh)?;
+
+        // The user might have been deleted since the token was issued
+        let user = match self.user_service.find_user(principal.user_id).await {
+            Ok(user) => user,
+            Err(errors::Error::NotFound(_)) => return Err(Error::InvalidToken.into()),
+            Err(err) => return Err(err),
+        };
+
+        self.issue_token(user).await
+    }
+}
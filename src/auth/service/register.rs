@@ -0,0 +1,30 @@
+use super::{RegisterInput, Service};
+use crate::{
+    auth::{
+        entities::AuthToken,
+        password::{self, MIN_PASSWORD_LENGTH},
+    },
+    errors::{self, core::Error},
+    user::CreateUserInput,
+};
+
+impl Service {
+    pub async fn register(&self, input: RegisterInput) -> Result<AuthToken, errors::Error> {
+        if input.password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(Error::PasswordTooShort {
+                min: MIN_PASSWORD_LENGTH,
+            }
+            .into());
+        }
+
+        let password_hash = password::hash(&input.password)?;
+        let user_input = CreateUserInput {
+            name: input.name,
+            full_name: input.full_name,
+            password_hash: Some(password_hash),
+        };
+        let user = self.user_service.create_user(user_input).await?;
+
+        self.issue_token(user).await
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::Utc;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::entities::Principal;
+use crate::{config, errors::core::Error, user::entities::Role};
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    Access,
+    Refresh,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    /// The user id
+    pub sub: String,
+    pub name: String,
+    #[serde(default)]
+    pub roles: Vec<Role>,
+    pub kind: TokenKind,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Signs and verifies JWTs with the configured secret
+pub struct Keys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    pub access_token_ttl: i64,
+    pub refresh_token_ttl: i64,
+}
+
+impl Keys {
+    pub fn new(jwt: &config::Jwt) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(jwt.secret.expose().as_bytes()),
+            decoding: DecodingKey::from_secret(jwt.secret.expose().as_bytes()),
+            access_token_ttl: jwt.access_token_ttl,
+            refresh_token_ttl: jwt.refresh_token_ttl,
+        }
+    }
+
+    pub fn encode(&self, principal: &Principal, kind: TokenKind) -> Result<String, Error> {
+        let ttl = match kind {
+            TokenKind::Access => self.access_token_ttl,
+            TokenKind::Refresh => self.refresh_token_ttl,
+        };
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: principal.user_id.to_string(),
+            name: principal.name.clone(),
+            roles: principal.roles.clone(),
+            kind,
+            iat: now,
+            exp: now + ttl,
+        };
+
+        encode(&Header::default(), &claims, &self.encoding).map_err(|err| {
+            log::error!("encoding token: {}", err);
+            Error::Internal
+        })
+    }
+
+    /// Decode and validate a token, making sure it is of the expected kind
+    pub fn decode(&self, token: &str, kind: TokenKind) -> Result<Principal, Error> {
+        let claims = decode::<Claims>(token, &self.decoding, &Validation::default())?.claims;
+        if claims.kind != kind {
+            return Err(Error::InvalidToken);
+        }
+        let user_id = claims
+            .sub
+            .parse::<Uuid>()
+            .map_err(|_| Error::InvalidToken)?;
+
+        Ok(Principal {
+            user_id,
+            name: claims.name,
+            roles: claims.roles,
+        })
+    }
+}
+
+impl std::fmt::Debug for Keys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keys")
+            .field("access_token_ttl", &self.access_token_ttl)
+            .field("refresh_token_ttl", &self.refresh_token_ttl)
+            .finish_non_exhaustive()
+    }
+}
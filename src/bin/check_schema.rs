@@ -0,0 +1,52 @@
+//! Compares a previous GraphQL schema to the current one.
+//! Each change is classified as breaking, dangerous or safe for existing clients.
+//! Exits with an error when any change is breaking, dangerous changes are only reported.
+//!
+//! `cargo run --bin check_schema -- previous.graphql [current.graphql]`
+//!
+//! The current schema defaults to the compiled one.
+
+use std::{env, fs, process};
+
+use graph::schema::{self, diff};
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let previous = match args.next() {
+        Some(path) => read(&path),
+        None => {
+            eprintln!("usage: check_schema <previous.graphql> [current.graphql]");
+            process::exit(2);
+        }
+    };
+    let current = match args.next() {
+        Some(path) => read(&path),
+        None => schema::build().finish().sdl(),
+    };
+
+    let changes = match diff::diff(&previous, &current) {
+        Ok(changes) => changes,
+        Err(err) => {
+            eprintln!("parsing schema: {}", err);
+            process::exit(2);
+        }
+    };
+
+    if changes.is_empty() {
+        println!("No changes");
+    }
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    if changes.iter().any(diff::Change::is_breaking) {
+        process::exit(1);
+    }
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|err| {
+        eprintln!("reading `{}`: {}", path, err);
+        process::exit(2);
+    })
+}
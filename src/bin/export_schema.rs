@@ -0,0 +1,10 @@
+//! Prints the GraphQL schema in SDL.
+//! It doesn't need a running server nor a database connection.
+//!
+//! `cargo run --bin export_schema > tests/schema.graphql`
+
+use graph::schema;
+
+fn main() {
+    print!("{}", schema::build().finish().sdl());
+}
@@ -0,0 +1,353 @@
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde_json::Value;
+
+use crate::Error;
+
+/// Prefix of the env vars overriding a key, e.g: `APP_DATABASE_URL` for `database.url`
+pub const ENV_PREFIX: &str = "APP_";
+
+/// Suffix of the env vars naming a file to read the value from, e.g: `DATABASE_URL_FILE`
+pub const FILE_SUFFIX: &str = "_FILE";
+
+/// Extensions of the config files, in the order they are looked up
+const FILE_EXTENSIONS: &[&str] = &["toml", "yaml", "yml"];
+
+/// Where a value comes from, named in the errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(String),
+    /// A `_FILE` env var, and the file it names
+    EnvFile {
+        var: String,
+        path: PathBuf,
+    },
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "the defaults"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "env var {}", var),
+            Source::EnvFile { var, path } => write!(f, "{} (env var {})", path.display(), var),
+        }
+    }
+}
+
+/// The raw values of the configuration, keyed by their dotted path, e.g: `database.url`.
+/// A layer overrides the values of the layers merged before it.
+pub struct Layers {
+    keys: &'static [&'static str],
+    values: HashMap<&'static str, (String, Source)>,
+}
+
+// The values may be secrets, e.g: read from a `_FILE` env var. Only their sources are shown.
+impl fmt::Debug for Layers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut sources = f.debug_map();
+        for key in self.keys {
+            if let Some((_, source)) = self.values.get(key) {
+                sources.entry(key, source);
+            }
+        }
+        sources.finish()
+    }
+}
+
+impl Layers {
+    /// Only `keys` are accepted in the files
+    pub fn new(keys: &'static [&'static str]) -> Self {
+        Self {
+            keys,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Merges `{dir}/{name}.toml`, or its `yaml`/`yml` counterpart. A missing file is not an error.
+    pub fn merge_file(&mut self, dir: &Path, name: &str) -> Result<(), Error> {
+        let path = FILE_EXTENSIONS
+            .iter()
+            .map(|extension| dir.join(format!("{}.{}", name, extension)))
+            .find(|path| path.is_file());
+        let path = match path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let content = fs::read_to_string(&path).map_err(|err| {
+            Error::InvalidArgument(format!("config: reading {}: {}", path.display(), err))
+        })?;
+        self.merge_str(&content, path)
+    }
+
+    pub(super) fn merge_str(&mut self, content: &str, path: PathBuf) -> Result<(), Error> {
+        let is_toml = path
+            .extension()
+            .map_or(false, |extension| extension == "toml");
+        let document: Value = if is_toml {
+            toml::from_str(content).map_err(|err| err.to_string())
+        } else {
+            serde_yaml::from_str(content).map_err(|err| err.to_string())
+        }
+        .map_err(|err| {
+            Error::InvalidArgument(format!("config: parsing {}: {}", path.display(), err))
+        })?;
+
+        let mut values = Vec::new();
+        flatten(String::new(), document, &mut values).map_err(|err| {
+            Error::InvalidArgument(format!("config: {} in {}", err, path.display()))
+        })?;
+
+        for (key, value) in values {
+            let key = self.key(&key).ok_or_else(|| {
+                Error::InvalidArgument(format!(
+                    "config: unknown key `{}` in {}",
+                    key,
+                    path.display()
+                ))
+            })?;
+            self.values.insert(key, (value, Source::File(path.clone())));
+        }
+        Ok(())
+    }
+
+    /// Merges the env vars of `legacy`, then the `APP_` ones. `legacy` maps a key to its env var.
+    /// Each env var can be replaced by its `_FILE` variant, see `read_env`.
+    pub fn merge_env(&mut self, legacy: &[(&'static str, &'static str)]) -> Result<(), Error> {
+        for (key, var) in legacy {
+            if let Some(value) = read_env(var)? {
+                self.values.insert(key, value);
+            }
+        }
+        for key in self.keys {
+            if let Some(value) = read_env(&env_var(key))? {
+                self.values.insert(key, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// The value of a key without a default
+    pub fn get<T>(&self, key: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.values.get(key) {
+            Some((value, source)) => parse(key, value, source),
+            None => Err(Error::NotFound(format!(
+                "config: `{}` is not set. Set it in a config file, or with the {} env var",
+                key,
+                env_var(key)
+            ))),
+        }
+    }
+
+    pub fn get_or<T>(&self, key: &str, default: T) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.values.get(key) {
+            Some((value, source)) => parse(key, value, source),
+            None => Ok(default),
+        }
+    }
+
+    /// An error naming the key and where its value comes from
+    pub fn invalid(&self, key: &str, message: impl fmt::Display) -> Error {
+        let source = self
+            .values
+            .get(key)
+            .map_or(&Source::Default, |(_, source)| source);
+        invalid(key, source, message)
+    }
+
+    fn key(&self, key: &str) -> Option<&'static str> {
+        self.keys.iter().copied().find(|known| *known == key)
+    }
+}
+
+/// `APP_` followed by the key in uppercase, e.g: `APP_JWT_ACCESS_TOKEN_TTL` for `jwt.access_token_ttl`
+pub fn env_var(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace('.', "_").to_uppercase())
+}
+
+/// The value of `var`, or the content of the file named by `{var}_FILE`, e.g: a Docker or Kubernetes secret
+fn read_env(var: &str) -> Result<Option<(String, Source)>, Error> {
+    let file_var = format!("{}{}", var, FILE_SUFFIX);
+    match (std::env::var(var), std::env::var(&file_var)) {
+        (Ok(_), Ok(_)) => Err(Error::InvalidArgument(format!(
+            "config: {} and {} are both set, only one is allowed",
+            var, file_var
+        ))),
+        (Ok(value), Err(_)) => Ok(Some((value, Source::Env(var.to_string())))),
+        (Err(_), Ok(path)) => {
+            let value = fs::read_to_string(&path).map_err(|err| {
+                Error::InvalidArgument(format!(
+                    "config: reading {} from env var {}: {}",
+                    path, file_var, err
+                ))
+            })?;
+            // Secret files usually end with a newline
+            let value = value.trim_end_matches(&['\r', '\n'][..]).to_string();
+            let source = Source::EnvFile {
+                var: file_var,
+                path: PathBuf::from(path),
+            };
+            Ok(Some((value, source)))
+        }
+        (Err(_), Err(_)) => Ok(None),
+    }
+}
+
+fn parse<T>(key: &str, value: &str, source: &Source) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value
+        .parse::<T>()
+        .map_err(|err| invalid(key, source, format!("invalid value `{}` ({})", value, err)))
+}
+
+fn invalid(key: &str, source: &Source, message: impl fmt::Display) -> Error {
+    Error::InvalidArgument(format!("config: `{}` from {}: {}", key, source, message))
+}
+
+/// Tables become dotted keys, the other values are kept as strings to be parsed like env vars
+fn flatten(prefix: String, value: Value, values: &mut Vec<(String, String)>) -> Result<(), String> {
+    match value {
+        Value::Object(table) => {
+            for (name, value) in table {
+                let key = if prefix.is_empty() {
+                    name
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                flatten(key, value, values)?;
+            }
+        }
+        Value::Array(_) => return Err(format!("`{}` can't be a list", prefix)),
+        // `key:` in YAML, same as not setting it
+        Value::Null => {}
+        Value::String(value) => values.push((prefix, value)),
+        value => values.push((prefix, value.to_string())),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: &[&str] = &[
+        "env",
+        "http.port",
+        "graphql.max_depth",
+        "graphql.introspection",
+    ];
+
+    fn layers(content: &str, file: &str) -> Result<Layers, Error> {
+        let mut layers = Layers::new(KEYS);
+        layers.merge_str(content, PathBuf::from(file))?;
+        Ok(layers)
+    }
+
+    #[test]
+    fn toml_tables() -> Result<(), Error> {
+        let layers = layers(
+            "env = \"staging\"\n[http]\nport = 8080\n[graphql]\nintrospection = false\n",
+            "config/default.toml",
+        )?;
+        assert_eq!(layers.get::<String>("env")?, "staging");
+        assert_eq!(layers.get::<u16>("http.port")?, 8080);
+        assert!(!layers.get::<bool>("graphql.introspection")?);
+        assert_eq!(layers.get_or::<usize>("graphql.max_depth", 16)?, 16);
+        Ok(())
+    }
+
+    #[test]
+    fn yaml_mappings() -> Result<(), Error> {
+        let layers = layers(
+            "http:\n  port: 8080\ngraphql:\n  max_depth:\n",
+            "config/dev.yaml",
+        )?;
+        assert_eq!(layers.get::<u16>("http.port")?, 8080);
+        assert_eq!(layers.get_or::<usize>("graphql.max_depth", 16)?, 16);
+        Ok(())
+    }
+
+    #[test]
+    fn later_layer_wins() -> Result<(), Error> {
+        let mut layers = layers("[http]\nport = 8080\n", "config/default.toml")?;
+        layers.merge_str(
+            "[http]\nport = 9090\n",
+            PathBuf::from("config/production.toml"),
+        )?;
+        assert_eq!(layers.get::<u16>("http.port")?, 9090);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_key() {
+        let err = layers("[graphql]\nmax_dept = 8\n", "config/default.toml").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "config: unknown key `graphql.max_dept` in config/default.toml"
+        );
+    }
+
+    #[test]
+    fn invalid_value() -> Result<(), Error> {
+        let layers = layers("[http]\nport = \"eighty\"\n", "config/default.toml")?;
+        let err = layers.get::<u16>("http.port").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "config: `http.port` from config/default.toml: invalid value `eighty` (invalid digit found in string)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn env_file() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("nahla_config_env_file");
+        fs::write(&path, "s3cr3t\n")?;
+        std::env::set_var("NAHLA_CONFIG_ENV_FILE_FILE", &path);
+
+        let (value, source) = read_env("NAHLA_CONFIG_ENV_FILE")?.expect("value");
+        assert_eq!(value, "s3cr3t");
+        assert_eq!(
+            source,
+            Source::EnvFile {
+                var: String::from("NAHLA_CONFIG_ENV_FILE_FILE"),
+                path: path.clone(),
+            }
+        );
+
+        std::env::set_var("NAHLA_CONFIG_ENV_FILE", "s3cr3t");
+        assert!(read_env("NAHLA_CONFIG_ENV_FILE").is_err());
+
+        std::env::remove_var("NAHLA_CONFIG_ENV_FILE");
+        std::env::remove_var("NAHLA_CONFIG_ENV_FILE_FILE");
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn missing_value() {
+        let err = Layers::new(KEYS).get::<String>("env").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "config: `env` is not set. Set it in a config file, or with the APP_ENV env var"
+        );
+    }
+}
@@ -1,9 +1,15 @@
+mod unit_of_work;
+
 use std::time::Duration;
 
 use sqlx::{self, postgres::PgPoolOptions, Executor, Pool, Postgres, Transaction};
+pub use unit_of_work::UnitOfWork;
 
 use crate::{config, Error};
 
+/// Postgres `unique_violation`, see https://www.postgresql.org/docs/current/errcodes-appendix.html
+const UNIQUE_VIOLATION: &str = "23505";
+
 pub type DB = Pool<Postgres>;
 pub trait Queryer<'c>: Executor<'c, Database = sqlx::Postgres> {}
 
@@ -20,7 +26,7 @@ pub async fn connect(database: &config::Database) -> Result<DB, Error> {
     PgPoolOptions::new()
         .max_connections(database.pool_size)
         .max_lifetime(Duration::from_secs(30 * 60)) // 30 mins
-        .connect(&database.url)
+        .connect(database.url.expose())
         .await
         .map_err(|err| {
             log::error!("db: connecting to DB: {}", err);
@@ -39,3 +45,11 @@ pub async fn migrate(db: &DB) -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Returns true if the statement failed on a unique constraint
+pub fn is_unique_violation(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(err) => err.code().as_deref() == Some(UNIQUE_VIOLATION),
+        _ => false,
+    }
+}
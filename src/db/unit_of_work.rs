@@ -0,0 +1,41 @@
+use sqlx::{Postgres, Transaction};
+
+use super::DB;
+use crate::Error;
+
+/// Repository calls that succeed or fail together, in one transaction.
+/// Dropping it without `commit` rolls the transaction back.
+pub struct UnitOfWork {
+    tx: Transaction<'static, Postgres>,
+}
+
+impl UnitOfWork {
+    pub async fn begin(db: &DB) -> Result<Self, Error> {
+        match db.begin().await {
+            Ok(tx) => Ok(Self { tx }),
+            Err(err) => {
+                log::error!("db: beginning transaction: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Passed to repository calls, e.g: `repo.find_user_by_id(uow.tx(), id)`
+    pub fn tx(&mut self) -> &mut Transaction<'static, Postgres> {
+        &mut self.tx
+    }
+
+    pub async fn commit(self) -> Result<(), Error> {
+        self.tx.commit().await.map_err(|err| {
+            log::error!("db: committing transaction: {}", err);
+            err.into()
+        })
+    }
+
+    pub async fn rollback(self) -> Result<(), Error> {
+        self.tx.rollback().await.map_err(|err| {
+            log::error!("db: rolling back transaction: {}", err);
+            err.into()
+        })
+    }
+}
@@ -6,12 +6,22 @@ use sqlx;
 pub enum Error {
     // Other
     Internal,
-    MissingFirstAndLastPaginationArguments,
     PassedFirstAndLastPaginationArguments,
+    PaginationArgumentOutOfRange { max: i32 },
+    InvalidNodeId,
 
     // User
     UserNotFound,
     UsernameAlreadyExists,
+    UsernameRequired,
+    UserIdArguments,
+
+    // Auth
+    AuthenticationRequired,
+    PermissionDenied,
+    InvalidCredentials,
+    InvalidToken,
+    PasswordTooShort { min: usize },
 }
 
 impl std::convert::From<Error> for crate::Error {
@@ -22,16 +32,41 @@ impl std::convert::From<Error> for crate::Error {
             Error::UsernameAlreadyExists => {
                 crate::Error::AlreadyExists(String::from("username is already in use"))
             }
+            Error::UsernameRequired => {
+                crate::Error::InvalidArgument(String::from("username can't be null"))
+            }
+            Error::UserIdArguments => crate::Error::InvalidArgument(String::from(
+                "exactly one of `id` and `nodeId` must be given",
+            )),
+
+            // Auth
+            Error::AuthenticationRequired => {
+                crate::Error::Unauthenticated(String::from("authentication required"))
+            }
+            Error::PermissionDenied => {
+                crate::Error::PermissionDenied(String::from("permission denied"))
+            }
+            Error::InvalidCredentials => {
+                crate::Error::Unauthenticated(String::from("invalid username or password"))
+            }
+            Error::InvalidToken => {
+                crate::Error::Unauthenticated(String::from("invalid or expired token"))
+            }
+            Error::PasswordTooShort { min } => crate::Error::InvalidArgument(format!(
+                "password must be at least {} characters long",
+                min
+            )),
 
             // Other
             Error::Internal => crate::Error::Internal(String::new()),
-            Error::MissingFirstAndLastPaginationArguments => crate::Error::InvalidArgument(
-                "You must provide a `first` or `last` value to properly paginate the entity."
-                    .to_string(),
-            ),
             Error::PassedFirstAndLastPaginationArguments => crate::Error::InvalidArgument(
                 "Passing both `first` and `last` for pagination is not supported.".to_string(),
             ),
+            Error::InvalidNodeId => crate::Error::InvalidArgument(String::from("invalid node id")),
+            Error::PaginationArgumentOutOfRange { max } => crate::Error::InvalidArgument(format!(
+                "`first` and `last` must be between 0 and {}.",
+                max
+            )),
         }
     }
 }
@@ -60,3 +95,19 @@ impl std::convert::From<std::num::TryFromIntError> for Error {
         }
     }
 }
+
+impl std::convert::From<jsonwebtoken::errors::Error> for Error {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        match err {
+            _ => Error::InvalidToken,
+        }
+    }
+}
+
+impl std::convert::From<argon2::password_hash::Error> for Error {
+    fn from(err: argon2::password_hash::Error) -> Self {
+        match err {
+            _ => Error::Internal,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub mod core;
 
+use async_graphql::ErrorExtensions;
 use thiserror::Error;
+use ulid::Ulid;
 
 use crate::relay;
 
@@ -12,6 +14,9 @@ pub enum Error {
     #[error("{0}")]
     NotFound(String),
 
+    #[error("{0}")]
+    Unauthenticated(String),
+
     #[error("{0}")]
     PermissionDenied(String),
 
@@ -22,6 +27,39 @@ pub enum Error {
     AlreadyExists(String),
 }
 
+impl Error {
+    /// Stable error code, exposed to GraphQL clients as `extensions.code`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Internal(_) => "INTERNAL",
+            Error::NotFound(_) => "NOT_FOUND",
+            Error::Unauthenticated(_) => "UNAUTHENTICATED",
+            Error::PermissionDenied(_) => "PERMISSION_DENIED",
+            Error::InvalidArgument(_) => "INVALID_ARGUMENT",
+            Error::AlreadyExists(_) => "ALREADY_EXISTS",
+        }
+    }
+}
+
+impl ErrorExtensions for Error {
+    fn extend(&self) -> async_graphql::Error {
+        match self {
+            // The underlying message might contain SQL or other implementation details.
+            // Log it with a correlation id, and only give the id to the client.
+            Error::Internal(message) => {
+                let correlation_id = Ulid::new().to_string();
+                log::error!("internal error [{}]: {}", correlation_id, message);
+                async_graphql::Error::new(self.to_string()).extend_with(|_, e| {
+                    e.set("code", self.code());
+                    e.set("correlationId", correlation_id);
+                })
+            }
+            _ => async_graphql::Error::new(self.to_string())
+                .extend_with(|_, e| e.set("code", self.code())),
+        }
+    }
+}
+
 impl std::convert::From<sqlx::Error> for Error {
     fn from(err: sqlx::Error) -> Self {
         match err {
@@ -0,0 +1,82 @@
+mod postgres;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::{db::DB, user::entities::User, Error};
+
+/// How many events a slow subscriber can lag behind before it starts missing them
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "user", rename_all = "snake_case")]
+pub enum Event {
+    UserCreated(User),
+    UserUpdated(User),
+    UserDeleted(User),
+}
+
+/// Event bus, every subscriber receives every published event.
+///
+/// With the Postgres backend, events are published with `NOTIFY` and every instance
+/// forwards the notifications to its own subscribers, including the publishing one.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+    publisher: Publisher,
+}
+
+#[derive(Debug, Clone)]
+enum Publisher {
+    Memory,
+    Postgres(DB),
+}
+
+impl EventBus {
+    /// In-process event bus
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self {
+            sender,
+            publisher: Publisher::Memory,
+        }
+    }
+
+    /// Event bus shared by every instance connected to the same database.
+    /// The listener connection is established before returning.
+    pub async fn postgres(db: DB) -> Result<Self, Error> {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+
+        let listener = postgres::listen(&db).await?;
+        tokio::spawn(postgres::forward(db.clone(), listener, sender.clone()));
+
+        Ok(Self {
+            sender,
+            publisher: Publisher::Postgres(db),
+        })
+    }
+
+    pub async fn publish(&self, event: Event) {
+        match &self.publisher {
+            Publisher::Memory => {
+                // Sending only fails if there is no subscriber, which is fine.
+                let _ = self.sender.send(event);
+            }
+            Publisher::Postgres(db) => {
+                if let Err(err) = postgres::notify(db, &event).await {
+                    log::error!("events: publishing event: {}", err);
+                }
+            }
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use sqlx::postgres::PgListener;
+use tokio::sync::broadcast;
+
+use super::Event;
+use crate::{db::DB, Error};
+
+const CHANNEL: &str = "app_events";
+
+const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Open a dedicated connection listening to the events channel
+pub async fn listen(db: &DB) -> Result<PgListener, Error> {
+    let mut listener = PgListener::connect_with(db).await?;
+    listener.listen(CHANNEL).await?;
+
+    Ok(listener)
+}
+
+pub async fn notify(db: &DB, event: &Event) -> Result<(), Error> {
+    let payload = serde_json::to_string(event)?;
+    sqlx::query("select pg_notify($1, $2)")
+        .bind(CHANNEL)
+        .bind(payload)
+        .execute(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Forward every notification to the local subscribers.
+/// Notifications sent while the listener is reconnecting are lost.
+pub async fn forward(db: DB, mut listener: PgListener, sender: broadcast::Sender<Event>) {
+    loop {
+        match listener.try_recv().await {
+            Ok(Some(notification)) => match serde_json::from_str::<Event>(notification.payload()) {
+                Ok(event) => {
+                    let _ = sender.send(event);
+                }
+                Err(err) => log::error!("events: decoding notification: {}", err),
+            },
+            Ok(None) => {
+                log::warn!("events: listener connection lost");
+                listener = reconnect(&db).await;
+            }
+            Err(err) => {
+                log::error!("events: receiving notification: {}", err);
+                listener = reconnect(&db).await;
+            }
+        }
+    }
+}
+
+async fn reconnect(db: &DB) -> PgListener {
+    let mut delay = MIN_RECONNECT_DELAY;
+    loop {
+        tokio::time::sleep(delay).await;
+        match listen(db).await {
+            Ok(listener) => {
+                log::info!("events: listener reconnected");
+                return listener;
+            }
+            Err(err) => {
+                log::error!("events: reconnecting listener: {}", err);
+                delay = std::cmp::min(delay * 2, MAX_RECONNECT_DELAY);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use async_graphql::{Context, Error, FieldResult, Object};
+use async_graphql::{Context, ErrorExtensions, FieldResult, Object};
 use axum::{response::IntoResponse, Json};
 
 use super::model::{Health, HealthResponse};
@@ -17,7 +17,7 @@ impl HealthQuery {
         let result = server_ctx.health_service.find_health().await;
         match result {
             Ok(res) => Ok(res.into()),
-            Err(err) => Err(Error::new(err.to_string())),
+            Err(err) => Err(err.extend()),
         }
     }
 }
@@ -1,7 +1,10 @@
+pub mod apq;
+pub mod auth;
 pub mod config;
 pub mod context;
 pub mod db;
 mod errors;
+pub mod events;
 pub mod health;
 pub mod logger;
 pub mod meta;
@@ -6,12 +6,19 @@ use std::{
 use axum::Server;
 use graph::{config::Config, logger, routes::app};
 
+/// Prints the effective config, secrets masked, instead of starting the server
+const ARG_PRINT_CONFIG: &str = "--print-config";
+
 #[tokio::main]
 async fn main() -> Result<(), graph::Error> {
     let config = Arc::new(Config::load()?);
+    if std::env::args().skip(1).any(|arg| arg == ARG_PRINT_CONFIG) {
+        print!("{}", config.to_toml()?);
+        return Ok(());
+    }
     logger::init(&config);
 
-    let app = app().await?;
+    let app = app(Arc::clone(&config)).await?;
 
     let host: IpAddr = config.base_url.parse()?;
     let port = config.http.port;
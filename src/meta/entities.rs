@@ -4,4 +4,11 @@ use async_graphql::SimpleObject;
 pub struct Meta {
     pub build: String,
     pub version: String,
+    pub pagination: Pagination,
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct Pagination {
+    pub default_page_size: i32,
+    pub max_page_size: i32,
 }
@@ -6,6 +6,8 @@ use crate::meta::entities;
 pub struct Meta {
     pub build: String,
     pub version: String,
+    /// Page size limits of connections, e.g: `users`
+    pub pagination: Pagination,
 }
 
 impl From<entities::Meta> for Meta {
@@ -13,6 +15,24 @@ impl From<entities::Meta> for Meta {
         Self {
             build: meta.build,
             version: meta.version,
+            pagination: meta.pagination.into(),
+        }
+    }
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct Pagination {
+    /// Page size used when neither `first` nor `last` is given
+    pub default_page_size: i32,
+    /// Largest `first` or `last` value accepted
+    pub max_page_size: i32,
+}
+
+impl From<entities::Pagination> for Pagination {
+    fn from(pagination: entities::Pagination) -> Self {
+        Self {
+            default_page_size: pagination.default_page_size,
+            max_page_size: pagination.max_page_size,
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use async_graphql::{Context, Error, FieldResult, Object};
+use async_graphql::{Context, ErrorExtensions, FieldResult, Object};
 
 use super::model::Meta;
 use crate::context::ServerContext;
@@ -16,7 +16,7 @@ impl MetaQuery {
         let result = server_ctx.meta_service.find_meta().await;
         match result {
             Ok(res) => Ok(res.into()),
-            Err(err) => Err(Error::new(err.to_string())),
+            Err(err) => Err(err.extend()),
         }
     }
 }
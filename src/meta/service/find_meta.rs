@@ -1,11 +1,18 @@
 use super::Service;
-use crate::{errors::Error, meta::entities::Meta};
+use crate::{
+    errors::Error,
+    meta::entities::{Meta, Pagination},
+};
 
 impl Service {
     pub async fn find_meta(&self) -> Result<Meta, Error> {
         let meta = Meta {
             build: option_env!("VCS_REVISION").unwrap_or("unknown").to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
+            pagination: Pagination {
+                default_page_size: self.pagination.default_page_size,
+                max_page_size: self.pagination.max_page_size,
+            },
         };
         Ok(meta)
     }
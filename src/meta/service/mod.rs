@@ -1,17 +1,15 @@
 mod find_meta;
 
-#[derive(Debug)]
-pub struct Service;
+use crate::config;
 
-impl Service {
-    pub fn new() -> Self {
-        Self {}
-    }
+#[derive(Debug)]
+pub struct Service {
+    pagination: config::Pagination,
 }
 
-impl Default for Service {
-    fn default() -> Self {
-        Self::new()
+impl Service {
+    pub fn new(pagination: config::Pagination) -> Self {
+        Self { pagination }
     }
 }
 
@@ -0,0 +1,94 @@
+use async_graphql::SimpleObject;
+
+use super::Cursor;
+
+#[derive(Debug, Default, SimpleObject)]
+pub struct PageInfo {
+    // When paginating forwards, the cursor to continue.
+    pub end_cursor: Option<String>,
+    // When paginating forwards, are there more items?
+    pub has_next_page: bool,
+    // When paginating backwards, the cursor to continue.
+    pub start_cursor: Option<String>,
+    // When paginating backwards, are there more items?
+    pub has_previous_page: bool,
+}
+
+/// An edge of a `Connection`, exposed by the connections of each type, e.g: `UserEdge`
+#[derive(Debug)]
+pub struct Edge<T> {
+    // The item at the end of the edge.
+    pub node: T,
+    // A cursor for use in pagination.
+    pub cursor: String,
+}
+
+impl<T> Edge<T> {
+    pub fn new<C: Cursor>(node: T, cursor: &C) -> Self {
+        Self {
+            node,
+            cursor: cursor.encode(),
+        }
+    }
+}
+
+/// A page of items, exposed by the connections of each type, e.g: `UserConnection`
+#[derive(Debug)]
+pub struct Connection<T> {
+    // A list of edges.
+    pub edges: Vec<Edge<T>>,
+    // Information to aid in pagination.
+    pub page_info: PageInfo,
+}
+
+impl<T> Connection<T> {
+    /// Builds a connection from rows fetched with one extra row, see `KeysetQuery::page`.
+    /// The extra row only signals that there is a next (or previous, with `last`) page.
+    pub fn from_rows<R, C, F>(
+        mut rows: Vec<R>,
+        first: Option<i32>,
+        last: Option<i32>,
+        cursor: F,
+    ) -> Self
+    where
+        R: Into<T>,
+        C: Cursor,
+        F: Fn(&R) -> C,
+    {
+        let mut page_info = PageInfo::default();
+
+        match (first, last) {
+            (None, Some(last)) => {
+                // Rows come in order, so the extra row is the first one
+                if rows.len() > limit(last) {
+                    rows.remove(0);
+                    page_info.has_previous_page = true;
+                }
+            }
+            (first, _) => {
+                let first = limit(first.unwrap_or_default());
+                if rows.len() > first {
+                    rows.truncate(first);
+                    page_info.has_next_page = true;
+                }
+            }
+        };
+
+        let edges: Vec<Edge<T>> = rows
+            .into_iter()
+            .map(|row| {
+                let cursor = cursor(&row);
+                Edge::new(row.into(), &cursor)
+            })
+            .collect();
+
+        page_info.start_cursor = edges.first().map(|edge| edge.cursor.clone());
+        page_info.end_cursor = edges.last().map(|edge| edge.cursor.clone());
+
+        Self { edges, page_info }
+    }
+}
+
+fn limit(value: i32) -> usize {
+    usize::try_from(value).unwrap_or_default()
+}
@@ -0,0 +1,372 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use super::OrderDirection;
+
+/// The value of the column a connection is ordered by
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortValue {
+    Time(DateTime<Utc>),
+    Text(String),
+}
+
+/// Position of a row in a keyset ordering.
+/// `value` is `None` when the connection is ordered by the id only.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyset {
+    pub value: Option<SortValue>,
+    pub id: Uuid,
+}
+
+/// Extra conditions of a connection query, e.g: filters.
+/// Each condition must be pushed as ` and <condition>`, binding every value.
+pub trait Conditions {
+    fn push_conditions(&self, query: &mut QueryBuilder<'static, Postgres>);
+}
+
+/// Builds parameterized keyset pagination queries.
+/// Rows are ordered by `column`, and by `id` as a tie-breaker.
+pub struct KeysetQuery<'a> {
+    table: &'static str,
+    column: &'static str,
+    direction: OrderDirection,
+    conditions: Option<&'a dyn Conditions>,
+}
+
+impl<'a> KeysetQuery<'a> {
+    pub fn new(table: &'static str, column: &'static str, direction: OrderDirection) -> Self {
+        Self {
+            table,
+            column,
+            direction,
+            conditions: None,
+        }
+    }
+
+    pub fn conditions(mut self, conditions: &'a dyn Conditions) -> Self {
+        self.conditions = Some(conditions);
+        self
+    }
+
+    /// Selects the rows between `after` and `before`, limited by `first` or `last`.
+    /// One extra row is fetched to tell whether there are more pages, see `Connection::from_rows`.
+    /// Callers must validate and resolve `first`/`last` beforehand, see `validation::validate_params`.
+    pub fn page(
+        &self,
+        first: Option<i32>,
+        after: Option<&Keyset>,
+        last: Option<i32>,
+        before: Option<&Keyset>,
+    ) -> QueryBuilder<'static, Postgres> {
+        let mut query = QueryBuilder::new("");
+
+        match (first, last) {
+            (None, Some(last)) => {
+                query.push("select * from ( ");
+                self.push_select(&mut query, "*", after, before);
+                self.push_order_by(&mut query, self.direction.reverse());
+                query.push(" limit ").push_bind(last + 1);
+                query.push(" ) as data");
+                self.push_order_by(&mut query, self.direction);
+            }
+            (first, _) => {
+                self.push_select(&mut query, "*", after, before);
+                self.push_order_by(&mut query, self.direction);
+                query
+                    .push(" limit ")
+                    .push_bind(first.unwrap_or_default() + 1);
+            }
+        };
+
+        query
+    }
+
+    /// Counts every row matching the conditions, regardless of the cursors
+    pub fn count(&self) -> QueryBuilder<'static, Postgres> {
+        let mut query = QueryBuilder::new("select count(*) from ( ");
+        self.push_select(&mut query, "id", None, None);
+        query.push(" ) as data");
+        query
+    }
+
+    /// Returns whether any row comes before the keyset, the keyset row included.
+    /// Used for `hasPreviousPage` when paginating with `after`.
+    pub fn exists_before(&self, keyset: &Keyset) -> QueryBuilder<'static, Postgres> {
+        self.exists(keyset, self.direction.reverse())
+    }
+
+    /// Returns whether any row comes after the keyset, the keyset row included.
+    /// Used for `hasNextPage` when paginating with `before`.
+    pub fn exists_after(&self, keyset: &Keyset) -> QueryBuilder<'static, Postgres> {
+        self.exists(keyset, self.direction)
+    }
+
+    fn exists(
+        &self,
+        keyset: &Keyset,
+        direction: OrderDirection,
+    ) -> QueryBuilder<'static, Postgres> {
+        let mut query = QueryBuilder::new("select exists ( ");
+        self.push_select(&mut query, "1", None, None);
+        self.push_keyset(&mut query, keyset, direction, true);
+        query.push(" )");
+        query
+    }
+
+    fn push_select(
+        &self,
+        query: &mut QueryBuilder<'static, Postgres>,
+        columns: &str,
+        after: Option<&Keyset>,
+        before: Option<&Keyset>,
+    ) {
+        query.push(format!("select {columns} from {} where true", self.table));
+        if let Some(conditions) = self.conditions {
+            conditions.push_conditions(query);
+        }
+        if let Some(after) = after {
+            self.push_keyset(query, after, self.direction, false);
+        }
+        if let Some(before) = before {
+            self.push_keyset(query, before, self.direction.reverse(), false);
+        }
+    }
+
+    /// Selects the rows that come after the keyset in the given direction.
+    /// `inclusive` also selects the keyset row itself.
+    fn push_keyset(
+        &self,
+        query: &mut QueryBuilder<'static, Postgres>,
+        keyset: &Keyset,
+        direction: OrderDirection,
+        inclusive: bool,
+    ) {
+        let operator = match (direction, inclusive) {
+            (OrderDirection::Asc, false) => ">",
+            (OrderDirection::Asc, true) => ">=",
+            (OrderDirection::Desc, false) => "<",
+            (OrderDirection::Desc, true) => "<=",
+        };
+
+        match &keyset.value {
+            None => {
+                query
+                    .push(format!(" and id {operator} "))
+                    .push_bind(keyset.id);
+            }
+            Some(value) => {
+                query.push(format!(" and ({}, id) {operator} (", self.column));
+                match value {
+                    SortValue::Time(time) => query.push_bind(*time),
+                    SortValue::Text(text) => query.push_bind(text.clone()),
+                };
+                query.push(", ").push_bind(keyset.id).push(")");
+            }
+        };
+    }
+
+    fn push_order_by(
+        &self,
+        query: &mut QueryBuilder<'static, Postgres>,
+        direction: OrderDirection,
+    ) {
+        let direction = direction.as_sql();
+        if self.column == "id" {
+            query.push(format!(" order by id {direction}"));
+        } else {
+            query.push(format!(
+                " order by {column} {direction}, id {direction}",
+                column = self.column
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::Execute;
+
+    use super::*;
+
+    struct NameContains(String);
+
+    impl Conditions for NameContains {
+        fn push_conditions(&self, query: &mut QueryBuilder<'static, Postgres>) {
+            query
+                .push(" and strpos(name, ")
+                .push_bind(self.0.clone())
+                .push(") > 0");
+        }
+    }
+
+    fn keyset() -> Keyset {
+        Keyset {
+            value: None,
+            id: Uuid::nil(),
+        }
+    }
+
+    fn name_keyset() -> Keyset {
+        Keyset {
+            value: Some(SortValue::Text("one".to_string())),
+            id: Uuid::nil(),
+        }
+    }
+
+    #[test]
+    fn first() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(Some(2), None, None, None);
+        assert_eq!(
+            sql.build().sql(),
+            "select * from user_ where true order by id asc limit $1"
+        );
+    }
+
+    #[test]
+    fn first_after() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(Some(2), Some(&keyset()), None, None);
+        assert_eq!(
+            sql.build().sql(),
+            "select * from user_ where true and id > $1 order by id asc limit $2"
+        );
+    }
+
+    #[test]
+    fn first_before() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(Some(2), None, None, Some(&keyset()));
+        assert_eq!(
+            sql.build().sql(),
+            "select * from user_ where true and id < $1 order by id asc limit $2"
+        );
+    }
+
+    #[test]
+    fn first_after_before() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(Some(2), Some(&keyset()), None, Some(&keyset()));
+        assert_eq!(
+            sql.build().sql(),
+            "select * from user_ where true and id > $1 and id < $2 order by id asc limit $3"
+        );
+    }
+
+    #[test]
+    fn last() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(None, None, Some(2), None);
+        assert_eq!(
+            sql.build().sql(),
+            "select * from ( select * from user_ where true order by id desc limit $1 ) as data order by id asc"
+        );
+    }
+
+    #[test]
+    fn last_before() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(None, None, Some(2), Some(&keyset()));
+        assert_eq!(
+            sql.build().sql(),
+            "select * from ( select * from user_ where true and id < $1 order by id desc limit $2 ) as data order by id asc"
+        );
+    }
+
+    #[test]
+    fn last_after() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(None, Some(&keyset()), Some(2), None);
+        assert_eq!(
+            sql.build().sql(),
+            "select * from ( select * from user_ where true and id > $1 order by id desc limit $2 ) as data order by id asc"
+        );
+    }
+
+    #[test]
+    fn last_after_before() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(None, Some(&keyset()), Some(2), Some(&keyset()));
+        assert_eq!(
+            sql.build().sql(),
+            "select * from ( select * from user_ where true and id > $1 and id < $2 order by id desc limit $3 ) as data order by id asc"
+        );
+    }
+
+    #[test]
+    fn no_first_no_last() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.page(None, None, None, None);
+        assert_eq!(
+            sql.build().sql(),
+            "select * from user_ where true order by id asc limit $1"
+        );
+    }
+
+    #[test]
+    fn order_by_column_desc() {
+        let query = KeysetQuery::new("user_", "name", OrderDirection::Desc);
+        let mut sql = query.page(Some(2), Some(&name_keyset()), None, None);
+        assert_eq!(
+            sql.build().sql(),
+            "select * from user_ where true and (name, id) < ($1, $2) order by name desc, id desc limit $3"
+        );
+
+        let mut sql = query.page(None, None, Some(2), Some(&name_keyset()));
+        assert_eq!(
+            sql.build().sql(),
+            "select * from ( select * from user_ where true and (name, id) > ($1, $2) order by name asc, id asc limit $3 ) as data order by name desc, id desc"
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        let name = NameContains("o".to_string());
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc).conditions(&name);
+        let mut sql = query.page(Some(2), Some(&keyset()), None, None);
+        assert_eq!(
+            sql.build().sql(),
+            "select * from user_ where true and strpos(name, $1) > 0 and id > $2 order by id asc limit $3"
+        );
+    }
+
+    #[test]
+    fn exists_before() {
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc);
+        let mut sql = query.exists_before(&keyset());
+        assert_eq!(
+            sql.build().sql(),
+            "select exists ( select 1 from user_ where true and id <= $1 )"
+        );
+
+        let query = KeysetQuery::new("user_", "name", OrderDirection::Desc);
+        let mut sql = query.exists_before(&name_keyset());
+        assert_eq!(
+            sql.build().sql(),
+            "select exists ( select 1 from user_ where true and (name, id) >= ($1, $2) )"
+        );
+    }
+
+    #[test]
+    fn exists_after() {
+        let name = NameContains("o".to_string());
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc).conditions(&name);
+        let mut sql = query.exists_after(&keyset());
+        assert_eq!(
+            sql.build().sql(),
+            "select exists ( select 1 from user_ where true and strpos(name, $1) > 0 and id >= $2 )"
+        );
+    }
+
+    #[test]
+    fn count() {
+        let name = NameContains("o".to_string());
+        let query = KeysetQuery::new("user_", "id", OrderDirection::Asc).conditions(&name);
+        let mut sql = query.count();
+        assert_eq!(
+            sql.build().sql(),
+            "select count(*) from ( select id from user_ where true and strpos(name, $1) > 0 ) as data"
+        );
+    }
+}
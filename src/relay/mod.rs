@@ -1,8 +1,69 @@
+mod connection;
+pub mod keyset;
+mod node;
 pub mod validation;
 
-use async_graphql::static_assertions::_core::fmt::Formatter;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use async_graphql::{static_assertions::_core::fmt::Formatter, Enum};
+pub use connection::{Connection, Edge, PageInfo};
+pub use node::{from_global_id, to_global_id};
+use serde::{de::DeserializeOwned, Serialize};
 use uuid::Uuid;
 
+/// Page size of the connections given neither `first` nor `last`, see `set_default_page_size`
+static DEFAULT_PAGE_SIZE: AtomicI32 = AtomicI32::new(1);
+
+/// The complexity of a field can't read the schema data, so the configured default page size
+/// is set along with the schema.
+pub fn set_default_page_size(page_size: i32) {
+    DEFAULT_PAGE_SIZE.store(page_size, Ordering::Relaxed);
+}
+
+/// Cost of a connection field: its fields are resolved once per item of the page
+pub fn connection_complexity(
+    first: Option<i32>,
+    last: Option<i32>,
+    child_complexity: usize,
+) -> usize {
+    let page_size = first
+        .or(last)
+        .unwrap_or_else(|| DEFAULT_PAGE_SIZE.load(Ordering::Relaxed))
+        .max(1);
+    usize::try_from(page_size).unwrap_or(1) * child_complexity
+}
+
+/// A cursor codec, turning a position in a connection into an opaque string and back
+pub trait Cursor: Sized {
+    fn encode(&self) -> String;
+    fn decode(s: &str) -> Result<Self, Base64CursorError>;
+}
+
+/// Possible directions in which to order a list of items.
+#[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OrderDirection {
+    /// Specifies an ascending order for a given `orderBy` argument.
+    Asc,
+    /// Specifies a descending order for a given `orderBy` argument.
+    Desc,
+}
+
+impl OrderDirection {
+    pub fn reverse(&self) -> Self {
+        match self {
+            OrderDirection::Asc => OrderDirection::Desc,
+            OrderDirection::Desc => OrderDirection::Asc,
+        }
+    }
+
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            OrderDirection::Asc => "asc",
+            OrderDirection::Desc => "desc",
+        }
+    }
+}
+
 /// Base64 invalid states, used by `Base64Cursor`.
 pub enum Base64CursorError {
     /// Invalid cursor. This can happen if the base64 string is valid, but its contents don't
@@ -20,17 +81,26 @@ impl std::fmt::Display for Base64CursorError {
 
 /// Base64 cursor implementation
 pub struct Base64Cursor {
-    name: &'static str,
+    name: String,
     index: Uuid,
 }
 impl Base64Cursor {
     pub fn new(index: Uuid) -> Self {
+        Self::with_name("Cursor", index)
+    }
+
+    /// Creates a cursor whose encoding is prefixed by `name`, e.g: `User:<uuid>`
+    pub fn with_name(name: &str, index: Uuid) -> Self {
         Self {
-            name: "Cursor",
+            name: name.to_string(),
             index,
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Returns a base64 string representation of the cursor
     pub fn encode(&self) -> String {
         base64::encode_config(
@@ -45,14 +115,22 @@ impl Base64Cursor {
             .map_err(Base64CursorError::DecodeError)?;
 
         let cursor = String::from_utf8(bytes).map_err(|_| Base64CursorError::Invalid)?;
-        let index = cursor
-            .split(':')
-            .last()
-            .map(|s| s.parse::<Uuid>())
-            .ok_or(Base64CursorError::Invalid)?
+        let (name, index) = cursor.split_once(':').ok_or(Base64CursorError::Invalid)?;
+        let index = index
+            .parse::<Uuid>()
             .map_err(|_| Base64CursorError::Invalid)?;
 
-        Ok(Self::new(index))
+        Ok(Self::with_name(name, index))
+    }
+}
+
+impl Cursor for Base64Cursor {
+    fn encode(&self) -> String {
+        self.encode()
+    }
+
+    fn decode(s: &str) -> Result<Self, Base64CursorError> {
+        Self::decode(s)
     }
 }
 
@@ -67,3 +145,19 @@ impl From<Base64Cursor> for Uuid {
         cursor.index
     }
 }
+
+/// Returns a base64 string representation of any serializable cursor.
+/// Used by connections whose cursor contains more than the id, e.g: the sort key.
+pub fn encode_cursor<T: Serialize>(cursor: &T) -> String {
+    // Serializing plain data structures can't fail
+    let json = serde_json::to_string(cursor).unwrap_or_default();
+    base64::encode_config(json, base64::URL_SAFE_NO_PAD)
+}
+
+/// Decodes a base64 string created by `encode_cursor`
+pub fn decode_cursor<T: DeserializeOwned>(s: &str) -> Result<T, Base64CursorError> {
+    let bytes = base64::decode_config(s, base64::URL_SAFE_NO_PAD)
+        .map_err(Base64CursorError::DecodeError)?;
+
+    serde_json::from_slice(&bytes).map_err(|_| Base64CursorError::Invalid)
+}
@@ -0,0 +1,16 @@
+use async_graphql::ID;
+use uuid::Uuid;
+
+use super::Base64Cursor;
+use crate::errors::core::Error;
+
+/// Returns the global id of an object: `base64("<type>:<uuid>")`
+pub fn to_global_id(type_name: &str, id: Uuid) -> ID {
+    ID(Base64Cursor::with_name(type_name, id).encode())
+}
+
+/// Returns the type name and the id encoded in a global id
+pub fn from_global_id(id: &ID) -> Result<(String, Uuid), Error> {
+    let cursor = Base64Cursor::decode(id).map_err(|_| Error::InvalidNodeId)?;
+    Ok((cursor.name().to_string(), cursor.into()))
+}
@@ -1,38 +1,40 @@
-use uuid::Uuid;
-
 use crate::{
+    config,
     errors::{
-        core::Error::{
-            MissingFirstAndLastPaginationArguments, PassedFirstAndLastPaginationArguments,
-        },
+        core::Error::{PaginationArgumentOutOfRange, PassedFirstAndLastPaginationArguments},
         Error,
     },
-    relay::Base64Cursor,
+    relay::Cursor,
 };
 
 /// Parse `after` and `befor` to cursor
-pub fn convert_params(
+pub fn convert_params<C: Cursor>(
     after: Option<String>,
     before: Option<String>,
-) -> Result<(Option<Uuid>, Option<Uuid>), Error> {
-    let (after_uuid, before_uuid) = match (after, before) {
-        (None, None) => (None, None),
-        (Some(after), Some(before)) => (
-            Some(Base64Cursor::decode(&after)?.into()),
-            Some(Base64Cursor::decode(&before)?.into()),
-        ),
-        (Some(after), None) => (Some(Base64Cursor::decode(&after)?.into()), None),
-        (None, Some(before)) => (None, Some(Base64Cursor::decode(&before)?.into())),
-    };
-    Ok((after_uuid, before_uuid))
+) -> Result<(Option<C>, Option<C>), Error> {
+    let after_cursor = after.map(|after| C::decode(&after)).transpose()?;
+    let before_cursor = before.map(|before| C::decode(&before)).transpose()?;
+    Ok((after_cursor, before_cursor))
 }
 
-pub fn validate_params(first: Option<i32>, last: Option<i32>) -> Result<(), Error> {
-    match (first, last) {
-        (None, None) => return Err(MissingFirstAndLastPaginationArguments.into()),
+/// Returns `first` and `last`, `first` being the default page size when neither is given
+pub fn validate_params(
+    first: Option<i32>,
+    last: Option<i32>,
+    pagination: &config::Pagination,
+) -> Result<(Option<i32>, Option<i32>), Error> {
+    let (first, last) = match (first, last) {
+        (None, None) => (Some(pagination.default_page_size), None),
         (Some(_), Some(_)) => return Err(PassedFirstAndLastPaginationArguments.into()),
-        _ => (),
+        params => params,
     };
 
-    Ok(())
+    let max = pagination.max_page_size;
+    if let Some(size) = first.or(last) {
+        if !(0..=max).contains(&size) {
+            return Err(PaginationArgumentOutOfRange { max }.into());
+        }
+    }
+
+    Ok((first, last))
 }
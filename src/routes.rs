@@ -1,13 +1,14 @@
 use std::sync::Arc;
 
 use async_graphql::{
-    http::{playground_source, GraphQLPlaygroundConfig},
-    EmptySubscription, Schema,
+    dataloader::DataLoader,
+    extensions::apollo_persisted_queries::{ApolloPersistedQueries, LruCacheStorage},
+    http::{playground_source, GraphQLPlaygroundConfig, ALL_WEBSOCKET_PROTOCOLS},
 };
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::{
-    extract::Extension,
-    response::{self, IntoResponse},
+    extract::{ws::WebSocketUpgrade, Extension},
+    response::{self, IntoResponse, Response},
     routing::{get, post},
     Router,
 };
@@ -15,41 +16,198 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
+    apq,
+    auth::{self, extractor::Auth},
     config,
     config::Config,
     context::ServerContext,
-    db, health, meta, routes,
-    schema::{AppSchema, Mutation, Query},
+    db::{self, DB},
+    events::EventBus,
+    health, meta, relay, routes,
+    schema::{self, AppSchema},
     user, Error,
 };
 
-pub async fn graphql_handler(schema: Extension<AppSchema>, req: GraphQLRequest) -> GraphQLResponse {
-    schema.execute(req.into_inner()).await.into()
+pub async fn graphql_handler(
+    schema: Extension<AppSchema>,
+    Auth(principal): Auth,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    let mut req = req.into_inner();
+    if let Some(principal) = principal {
+        req = req.data(principal);
+    }
+    schema.execute(req).await.into()
+}
+/// Serves both `graphql-ws` and `graphql-transport-ws` protocols.
+/// The token goes in the `connection_init` payload, see `auth::extractor::connection_init_data`.
+pub async fn graphql_ws_handler(
+    Extension(schema): Extension<AppSchema>,
+    Extension(server_ctx): Extension<Arc<ServerContext>>,
+    protocol: GraphQLProtocol,
+    websocket: WebSocketUpgrade,
+) -> Response {
+    websocket
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .on_connection_init(move |payload| async move {
+                    auth::extractor::connection_init_data(&server_ctx, &payload)
+                })
+                .serve()
+        })
 }
 pub async fn graphql_playground() -> impl IntoResponse {
-    response::Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+    response::Html(playground_source(
+        GraphQLPlaygroundConfig::new("/graphql").subscription_endpoint("/graphql/ws"),
+    ))
 }
 
-pub async fn app() -> Result<Router, Error> {
-    let config = Arc::new(Config::load()?);
-
+/// Connects to the database, runs the migrations, and builds the app with the default services
+pub async fn app(config: Arc<Config>) -> Result<Router, Error> {
     let db = db::connect(&config.database).await?;
     db::migrate(&db).await?;
 
-    let user_service = Arc::new(user::Service::new(db.clone()));
-    let meta_service = Arc::new(meta::Service::new());
-    let health_service = Arc::new(health::Service::new());
+    AppBuilder::new(config, db).build().await
+}
+
+/// Builds the app from a given config and database, e.g: to embed it in another binary.
+/// Services that are not overridden are created from the config.
+/// It doesn't run the migrations, see `db::migrate`.
+pub struct AppBuilder {
+    config: Arc<Config>,
+    db: DB,
+    user_service: Option<Arc<user::Service>>,
+    auth_service: Option<Arc<auth::Service>>,
+    meta_service: Option<Arc<meta::Service>>,
+    health_service: Option<Arc<health::Service>>,
+}
+
+impl AppBuilder {
+    pub fn new(config: Arc<Config>, db: DB) -> Self {
+        Self {
+            config,
+            db,
+            user_service: None,
+            auth_service: None,
+            meta_service: None,
+            health_service: None,
+        }
+    }
+
+    /// The default `auth::Service` uses this one too
+    pub fn user_service(mut self, user_service: Arc<user::Service>) -> Self {
+        self.user_service = Some(user_service);
+        self
+    }
+
+    pub fn auth_service(mut self, auth_service: Arc<auth::Service>) -> Self {
+        self.auth_service = Some(auth_service);
+        self
+    }
+
+    pub fn meta_service(mut self, meta_service: Arc<meta::Service>) -> Self {
+        self.meta_service = Some(meta_service);
+        self
+    }
+
+    pub fn health_service(mut self, health_service: Arc<health::Service>) -> Self {
+        self.health_service = Some(health_service);
+        self
+    }
+
+    pub async fn build(self) -> Result<Router, Error> {
+        let (config, schema, server_context) = self.build_parts().await?;
+        Ok(router(&config, schema, server_context))
+    }
+
+    /// The schema the app serves, e.g: to run subscriptions without a WebSocket
+    pub async fn build_schema(self) -> Result<AppSchema, Error> {
+        let (_, schema, _) = self.build_parts().await?;
+        Ok(schema)
+    }
+
+    async fn build_parts(self) -> Result<(Arc<Config>, AppSchema, Arc<ServerContext>), Error> {
+        let Self {
+            config,
+            db,
+            user_service,
+            auth_service,
+            meta_service,
+            health_service,
+        } = self;
+
+        let user_service = match user_service {
+            Some(user_service) => user_service,
+            None => {
+                let events = match config.events.backend {
+                    config::EventsBackend::Memory => EventBus::new(),
+                    config::EventsBackend::Postgres => EventBus::postgres(db.clone()).await?,
+                };
+                Arc::new(user::Service::new(
+                    db.clone(),
+                    events,
+                    config.pagination.clone(),
+                ))
+            }
+        };
+        let auth_service = auth_service.unwrap_or_else(|| {
+            Arc::new(auth::Service::new(&config.jwt, Arc::clone(&user_service)))
+        });
+        let meta_service =
+            meta_service.unwrap_or_else(|| Arc::new(meta::Service::new(config.pagination.clone())));
+        let health_service = health_service.unwrap_or_else(|| Arc::new(health::Service::new()));
+        let user_loader = DataLoader::new(
+            user::UserLoader::new(Arc::clone(&user_service)),
+            tokio::spawn,
+        );
+
+        let server_context = Arc::new(ServerContext {
+            user_service,
+            auth_service,
+            meta_service,
+            health_service,
+        });
+
+        let schema = schema(&config, &db, Arc::clone(&server_context), user_loader);
+        Ok((config, schema, server_context))
+    }
+}
 
-    let server_context = Arc::new(ServerContext {
-        user_service,
-        meta_service,
-        health_service,
-    });
+fn schema(
+    config: &Config,
+    db: &DB,
+    server_context: Arc<ServerContext>,
+    user_loader: DataLoader<user::UserLoader>,
+) -> AppSchema {
+    relay::set_default_page_size(config.pagination.default_page_size);
 
-    let schema = Schema::build(Query::default(), Mutation::default(), EmptySubscription)
-        .data(Arc::clone(&server_context))
-        .finish();
+    let mut schema_builder = schema::build()
+        .data(server_context)
+        .data(user_loader)
+        .limit_depth(config.graphql.max_depth)
+        .limit_complexity(config.graphql.max_complexity)
+        .limit_recursive_depth(config.graphql.max_recursive_depth);
+
+    // Must run before `ApolloPersistedQueries` resolves the hashes
+    if config.graphql.persisted_queries_only {
+        schema_builder = schema_builder.extension(apq::PersistedQueriesOnly);
+    }
+    schema_builder = match config.graphql.apq_store {
+        config::ApqStore::Memory => schema_builder.extension(ApolloPersistedQueries::new(
+            LruCacheStorage::new(config.graphql.apq_cache_size),
+        )),
+        config::ApqStore::Postgres => schema_builder.extension(ApolloPersistedQueries::new(
+            apq::PostgresStorage::new(db.clone()),
+        )),
+    };
+    if !config.graphql.introspection {
+        schema_builder = schema_builder.disable_introspection();
+    }
+    schema_builder.finish()
+}
 
+fn router(config: &Config, schema: AppSchema, server_context: Arc<ServerContext>) -> Router {
     #[derive(OpenApi)]
     #[openapi(
         handlers(
@@ -64,15 +222,16 @@ pub async fn app() -> Result<Router, Error> {
 
     let mut app = Router::new()
         .route("/graphql", post(routes::graphql_handler))
+        .route("/graphql/ws", get(routes::graphql_ws_handler))
         .route("/health", get(health::resolver::health));
-    if config.env != config::Env::Production {
-        app = app
-            .route("/playground", get(routes::graphql_playground))
-            .merge(
-                SwaggerUi::new("/swagger/*tail").url("/api-doc/openapi.json", ApiDoc::openapi()),
-            );
+    if config.graphql.playground {
+        app = app.route("/playground", get(routes::graphql_playground));
     }
-    let app = app.layer(Extension(schema));
-
-    Ok(app)
+    if config.openapi.enabled {
+        app = app.merge(
+            SwaggerUi::new("/swagger/*tail").url("/api-doc/openapi.json", ApiDoc::openapi()),
+        );
+    }
+    app.layer(Extension(schema))
+        .layer(Extension(server_context))
 }
@@ -0,0 +1,790 @@
+use std::{collections::BTreeMap, fmt};
+
+use async_graphql::{
+    parser::{
+        self, parse_schema,
+        types::{
+            BaseType, ConstDirective, DirectiveDefinition, DirectiveLocation, FieldDefinition,
+            InputValueDefinition, Type, TypeDefinition, TypeKind, TypeSystemDefinition,
+        },
+        Positioned,
+    },
+    Name, Value,
+};
+
+const BUILT_IN_SCALARS: [&str; 5] = ["Boolean", "Float", "ID", "Int", "String"];
+
+const BUILT_IN_DIRECTIVES: [&str; 4] = ["deprecated", "include", "skip", "specifiedBy"];
+
+/// Reason of a `@deprecated` directive without one, see the GraphQL spec
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Existing clients might stop working
+    Breaking,
+    /// Existing clients might get values they don't handle, e.g: a new enum value
+    Dangerous,
+    Safe,
+}
+
+/// A change between two versions of the schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub severity: Severity,
+    /// e.g: `User`, `User.name`, `Query.users.first`
+    pub path: String,
+    pub message: String,
+}
+
+impl Change {
+    fn new(severity: Severity, path: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+
+    fn breaking(path: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Breaking, path, message)
+    }
+
+    fn safe(path: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Safe, path, message)
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.severity == Severity::Breaking
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Breaking => "BREAKING",
+            Severity::Dangerous => "DANGEROUS",
+            Severity::Safe => "SAFE",
+        };
+        write!(f, "{severity} {}: {}", self.path, self.message)
+    }
+}
+
+/// Lists the changes from the `old` SDL to the `new` one.
+/// Ordering and formatting differences aren't changes.
+pub fn diff(old: &str, new: &str) -> Result<Vec<Change>, parser::Error> {
+    let old = definitions(old)?;
+    let new = definitions(new)?;
+    let mut changes = Vec::new();
+
+    for (name, old_type) in &old.types {
+        match new.types.get(name) {
+            Some(new_type) => diff_type(&mut changes, name, old_type, new_type),
+            None => changes.push(Change::breaking(name, "type removed")),
+        }
+    }
+    for name in new
+        .types
+        .keys()
+        .filter(|name| !old.types.contains_key(*name))
+    {
+        changes.push(Change::safe(name, "type added"));
+    }
+
+    for (name, old_directive) in &old.directives {
+        let path = format!("@{name}");
+        match new.directives.get(name) {
+            Some(new_directive) => {
+                diff_directive(&mut changes, &path, old_directive, new_directive)
+            }
+            None => changes.push(Change::breaking(&path, "directive removed")),
+        }
+    }
+    for name in new
+        .directives
+        .keys()
+        .filter(|name| !old.directives.contains_key(*name))
+    {
+        changes.push(Change::safe(&format!("@{name}"), "directive added"));
+    }
+
+    Ok(changes)
+}
+
+struct Definitions {
+    types: BTreeMap<String, TypeDefinition>,
+    directives: BTreeMap<String, DirectiveDefinition>,
+}
+
+/// The schema definition and the built-in directives are left out, not every SDL printer exports them
+fn definitions(sdl: &str) -> Result<Definitions, parser::Error> {
+    let document = parse_schema(sdl)?;
+    let mut definitions = Definitions {
+        types: BTreeMap::new(),
+        directives: BTreeMap::new(),
+    };
+
+    for definition in document.definitions {
+        match definition {
+            TypeSystemDefinition::Type(definition) => {
+                let name = definition.node.name.node.to_string();
+                if !BUILT_IN_SCALARS.contains(&name.as_str()) {
+                    definitions.types.insert(name, definition.node);
+                }
+            }
+            TypeSystemDefinition::Directive(definition) => {
+                let name = definition.node.name.node.to_string();
+                if !BUILT_IN_DIRECTIVES.contains(&name.as_str()) {
+                    definitions.directives.insert(name, definition.node);
+                }
+            }
+            TypeSystemDefinition::Schema(_) => {}
+        }
+    }
+
+    Ok(definitions)
+}
+
+fn diff_type(changes: &mut Vec<Change>, path: &str, old: &TypeDefinition, new: &TypeDefinition) {
+    diff_description(changes, path, &old.description, &new.description);
+
+    match (&old.kind, &new.kind) {
+        (TypeKind::Scalar, TypeKind::Scalar) => {}
+        (TypeKind::Object(old), TypeKind::Object(new)) => {
+            diff_names(
+                changes,
+                path,
+                "interface",
+                Severity::Safe,
+                names(&old.implements),
+                names(&new.implements),
+            );
+            diff_fields(changes, path, &old.fields, &new.fields);
+        }
+        (TypeKind::Interface(old), TypeKind::Interface(new)) => {
+            diff_names(
+                changes,
+                path,
+                "interface",
+                Severity::Safe,
+                names(&old.implements),
+                names(&new.implements),
+            );
+            diff_fields(changes, path, &old.fields, &new.fields);
+        }
+        (TypeKind::Union(old), TypeKind::Union(new)) => {
+            diff_names(
+                changes,
+                path,
+                "member",
+                Severity::Dangerous,
+                names(&old.members),
+                names(&new.members),
+            );
+        }
+        (TypeKind::Enum(old), TypeKind::Enum(new)) => {
+            let old_values: Vec<&str> = old
+                .values
+                .iter()
+                .map(|v| v.node.value.node.as_str())
+                .collect();
+            let new_values: Vec<&str> = new
+                .values
+                .iter()
+                .map(|v| v.node.value.node.as_str())
+                .collect();
+            diff_names(
+                changes,
+                path,
+                "value",
+                Severity::Dangerous,
+                old_values,
+                new_values,
+            );
+
+            for old_value in &old.values {
+                let old_value = &old_value.node;
+                if let Some(new_value) = new
+                    .values
+                    .iter()
+                    .find(|new_value| new_value.node.value.node == old_value.value.node)
+                {
+                    let value_path = format!("{path}.{}", old_value.value.node);
+                    diff_description(
+                        changes,
+                        &value_path,
+                        &old_value.description,
+                        &new_value.node.description,
+                    );
+                    diff_deprecation(
+                        changes,
+                        &value_path,
+                        &old_value.directives,
+                        &new_value.node.directives,
+                    );
+                }
+            }
+        }
+        (TypeKind::InputObject(old), TypeKind::InputObject(new)) => {
+            diff_input_values(changes, path, "input field", &old.fields, &new.fields);
+        }
+        (old, new) => changes.push(Change::breaking(
+            path,
+            format!("kind changed from `{}` to `{}`", kind(old), kind(new)),
+        )),
+    }
+}
+
+fn diff_fields(
+    changes: &mut Vec<Change>,
+    path: &str,
+    old: &[Positioned<FieldDefinition>],
+    new: &[Positioned<FieldDefinition>],
+) {
+    for old_field in old {
+        let old_field = &old_field.node;
+        let field_path = format!("{path}.{}", old_field.name.node);
+
+        let new_field = match new
+            .iter()
+            .find(|new| new.node.name.node == old_field.name.node)
+        {
+            Some(new_field) => &new_field.node,
+            None => {
+                changes.push(Change::breaking(&field_path, "field removed"));
+                continue;
+            }
+        };
+
+        diff_description(
+            changes,
+            &field_path,
+            &old_field.description,
+            &new_field.description,
+        );
+        diff_deprecation(
+            changes,
+            &field_path,
+            &old_field.directives,
+            &new_field.directives,
+        );
+        diff_field_type(
+            changes,
+            &field_path,
+            &old_field.ty.node,
+            &new_field.ty.node,
+            is_safe_output_change,
+        );
+        diff_input_values(
+            changes,
+            &field_path,
+            "argument",
+            &old_field.arguments,
+            &new_field.arguments,
+        );
+    }
+
+    for new_field in new {
+        if !old
+            .iter()
+            .any(|old| old.node.name.node == new_field.node.name.node)
+        {
+            changes.push(Change::safe(
+                &format!("{path}.{}", new_field.node.name.node),
+                "field added",
+            ));
+        }
+    }
+}
+
+/// Used for both arguments and input object fields
+fn diff_input_values(
+    changes: &mut Vec<Change>,
+    path: &str,
+    what: &str,
+    old: &[Positioned<InputValueDefinition>],
+    new: &[Positioned<InputValueDefinition>],
+) {
+    for old_value in old {
+        let old_value = &old_value.node;
+        let value_path = format!("{path}.{}", old_value.name.node);
+
+        let new_value = match new
+            .iter()
+            .find(|new| new.node.name.node == old_value.name.node)
+        {
+            Some(new_value) => &new_value.node,
+            None => {
+                changes.push(Change::breaking(&value_path, format!("{what} removed")));
+                continue;
+            }
+        };
+
+        diff_description(
+            changes,
+            &value_path,
+            &old_value.description,
+            &new_value.description,
+        );
+        diff_deprecation(
+            changes,
+            &value_path,
+            &old_value.directives,
+            &new_value.directives,
+        );
+        diff_field_type(
+            changes,
+            &value_path,
+            &old_value.ty.node,
+            &new_value.ty.node,
+            is_safe_input_change,
+        );
+
+        let old_default = old_value.default_value.as_ref().map(|v| v.node.to_string());
+        let new_default = new_value.default_value.as_ref().map(|v| v.node.to_string());
+        if old_default != new_default {
+            changes.push(Change::safe(
+                &value_path,
+                format!(
+                    "default value changed from `{}` to `{}`",
+                    old_default.unwrap_or_default(),
+                    new_default.unwrap_or_default()
+                ),
+            ));
+        }
+    }
+
+    for new_value in new {
+        let new_value = &new_value.node;
+        if old
+            .iter()
+            .any(|old| old.node.name.node == new_value.name.node)
+        {
+            continue;
+        }
+
+        let value_path = format!("{path}.{}", new_value.name.node);
+        // Clients that don't know about it can't send it
+        if !new_value.ty.node.nullable && new_value.default_value.is_none() {
+            changes.push(Change::breaking(
+                &value_path,
+                format!("required {what} added"),
+            ));
+        } else {
+            changes.push(Change::safe(&value_path, format!("{what} added")));
+        }
+    }
+}
+
+fn diff_directive(
+    changes: &mut Vec<Change>,
+    path: &str,
+    old: &DirectiveDefinition,
+    new: &DirectiveDefinition,
+) {
+    diff_description(changes, path, &old.description, &new.description);
+    diff_input_values(changes, path, "argument", &old.arguments, &new.arguments);
+
+    let old_locations = locations(&old.locations);
+    let new_locations = locations(&new.locations);
+    diff_names(
+        changes,
+        path,
+        "location",
+        Severity::Safe,
+        old_locations.iter().map(String::as_str).collect(),
+        new_locations.iter().map(String::as_str).collect(),
+    );
+}
+
+fn diff_field_type(
+    changes: &mut Vec<Change>,
+    path: &str,
+    old: &Type,
+    new: &Type,
+    is_safe: fn(&Type, &Type) -> bool,
+) {
+    if old.to_string() == new.to_string() {
+        return;
+    }
+
+    let message = format!("type changed from `{old}` to `{new}`");
+    if is_safe(old, new) {
+        changes.push(Change::safe(path, message));
+    } else {
+        changes.push(Change::breaking(path, message));
+    }
+}
+
+/// Clients read outputs, a value that can't be null anymore is still what they expect
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    (old.nullable || !new.nullable)
+        && match (&old.base, &new.base) {
+            (BaseType::Named(old), BaseType::Named(new)) => old == new,
+            (BaseType::List(old), BaseType::List(new)) => is_safe_output_change(old, new),
+            _ => false,
+        }
+}
+
+/// Clients send inputs, every value they used to send must still be accepted
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    (!old.nullable || new.nullable)
+        && match (&old.base, &new.base) {
+            (BaseType::Named(old), BaseType::Named(new)) => old == new,
+            (BaseType::List(old), BaseType::List(new)) => is_safe_input_change(old, new),
+            _ => false,
+        }
+}
+
+fn diff_description(
+    changes: &mut Vec<Change>,
+    path: &str,
+    old: &Option<Positioned<String>>,
+    new: &Option<Positioned<String>>,
+) {
+    let old = old.as_ref().map(|description| description.node.trim());
+    let new = new.as_ref().map(|description| description.node.trim());
+    if old != new {
+        changes.push(Change::safe(path, "description changed"));
+    }
+}
+
+/// Deprecating, or no longer deprecating, doesn't change what clients can send or receive
+fn diff_deprecation(
+    changes: &mut Vec<Change>,
+    path: &str,
+    old: &[Positioned<ConstDirective>],
+    new: &[Positioned<ConstDirective>],
+) {
+    match (deprecation(old), deprecation(new)) {
+        (None, Some(reason)) => changes.push(Change::safe(path, format!("deprecated: {reason}"))),
+        (Some(_), None) => changes.push(Change::safe(path, "no longer deprecated")),
+        (Some(old), Some(new)) if old != new => changes.push(Change::safe(
+            path,
+            format!("deprecation reason changed from `{old}` to `{new}`"),
+        )),
+        _ => {}
+    }
+}
+
+/// The reason of the `@deprecated` directive, if any
+fn deprecation(directives: &[Positioned<ConstDirective>]) -> Option<String> {
+    let directive = directives
+        .iter()
+        .find(|directive| directive.node.name.node == "deprecated")?;
+    let reason = directive
+        .node
+        .arguments
+        .iter()
+        .find(|(name, _)| name.node == "reason")
+        .and_then(|(_, reason)| match &reason.node {
+            Value::String(reason) => Some(reason.clone()),
+            _ => None,
+        });
+    Some(reason.unwrap_or_else(|| DEFAULT_DEPRECATION_REASON.to_string()))
+}
+
+/// `added` is the severity of the names in `new` only
+fn diff_names(
+    changes: &mut Vec<Change>,
+    path: &str,
+    what: &str,
+    added: Severity,
+    old: Vec<&str>,
+    new: Vec<&str>,
+) {
+    for name in old.iter().filter(|name| !new.contains(*name)) {
+        changes.push(Change::breaking(path, format!("{what} `{name}` removed")));
+    }
+    for name in new.iter().filter(|name| !old.contains(*name)) {
+        changes.push(Change::new(added, path, format!("{what} `{name}` added")));
+    }
+}
+
+fn names(names: &[Positioned<Name>]) -> Vec<&str> {
+    names.iter().map(|name| name.node.as_str()).collect()
+}
+
+/// As written in the SDL, e.g: `FIELD_DEFINITION`
+fn locations(locations: &[Positioned<DirectiveLocation>]) -> Vec<String> {
+    locations
+        .iter()
+        .map(|location| {
+            let mut name = String::new();
+            for (i, c) in format!("{:?}", location.node).chars().enumerate() {
+                if i > 0 && c.is_uppercase() {
+                    name.push('_');
+                }
+                name.push(c.to_ascii_uppercase());
+            }
+            name
+        })
+        .collect()
+}
+
+fn kind(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Scalar => "scalar",
+        TypeKind::Object(_) => "type",
+        TypeKind::Interface(_) => "interface",
+        TypeKind::Union(_) => "union",
+        TypeKind::Enum(_) => "enum",
+        TypeKind::InputObject(_) => "input",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+type Query {
+  user(id: UUID!): User!
+  users(first: Int, last: Int): [User!]!
+}
+
+type User {
+  id: UUID!
+  name: String!
+  fullName: String
+}
+
+input UpdateUserInput {
+  id: UUID!
+  name: String!
+  fullName: String
+}
+
+enum UserOrderField {
+  CREATED_AT
+  NAME
+}
+
+union SearchResult = User
+
+scalar UUID
+
+directive @auth(role: String) on FIELD_DEFINITION
+"#;
+
+    fn changes(new: &str) -> Vec<String> {
+        changes_between(SCHEMA, new)
+    }
+
+    fn changes_between(old: &str, new: &str) -> Vec<String> {
+        diff(old, new)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn same_schema() {
+        assert!(changes(SCHEMA).is_empty());
+    }
+
+    #[test]
+    fn ordering_and_formatting() {
+        let new = r#"
+            directive @auth(role: String) on FIELD_DEFINITION
+            scalar UUID
+            union SearchResult = User
+            enum UserOrderField { CREATED_AT NAME }
+            input UpdateUserInput { id: UUID! name: String! fullName: String }
+            type User { id: UUID! name: String! fullName: String }
+            type Query {
+              users(first: Int, last: Int): [User!]!
+              user(id: UUID!): User!
+            }
+        "#;
+        assert!(changes(new).is_empty());
+    }
+
+    #[test]
+    fn removed_field() {
+        // `User` comes first
+        let new = SCHEMA.replacen("  fullName: String\n", "", 1);
+        assert_eq!(changes(&new), vec!["BREAKING User.fullName: field removed"]);
+    }
+
+    #[test]
+    fn output_nullability() {
+        let new = SCHEMA.replace("user(id: UUID!): User!", "user(id: UUID!): User");
+        assert_eq!(
+            changes(&new),
+            vec!["BREAKING Query.user: type changed from `User!` to `User`"]
+        );
+
+        let new = SCHEMA.replacen("fullName: String\n", "fullName: String!\n", 1);
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE User.fullName: type changed from `String` to `String!`"]
+        );
+    }
+
+    #[test]
+    fn input_nullability() {
+        let new = SCHEMA.replace(
+            "input UpdateUserInput {\n  id: UUID!\n  name: String!\n",
+            "input UpdateUserInput {\n  id: UUID!\n  name: String\n",
+        );
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE UpdateUserInput.name: type changed from `String!` to `String`"]
+        );
+
+        let new = SCHEMA.replace("users(first: Int,", "users(first: Int!,");
+        assert_eq!(
+            changes(&new),
+            vec!["BREAKING Query.users.first: type changed from `Int` to `Int!`"]
+        );
+    }
+
+    #[test]
+    fn added_argument() {
+        let new = SCHEMA.replace("last: Int)", "last: Int, after: String)");
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE Query.users.after: argument added"]
+        );
+
+        let new = SCHEMA.replace("last: Int)", "last: Int, after: String!)");
+        assert_eq!(
+            changes(&new),
+            vec!["BREAKING Query.users.after: required argument added"]
+        );
+
+        let new = SCHEMA.replace("last: Int)", "last: Int, after: String! = \"\")");
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE Query.users.after: argument added"]
+        );
+    }
+
+    #[test]
+    fn enum_values() {
+        let new = SCHEMA.replace("  NAME\n", "  UPDATED_AT\n");
+        assert_eq!(
+            changes(&new),
+            vec![
+                "BREAKING UserOrderField: value `NAME` removed",
+                "DANGEROUS UserOrderField: value `UPDATED_AT` added",
+            ]
+        );
+    }
+
+    #[test]
+    fn union_members() {
+        let new = SCHEMA.replace(
+            "union SearchResult = User",
+            "union SearchResult = User | Query",
+        );
+        assert_eq!(
+            changes(&new),
+            vec!["DANGEROUS SearchResult: member `Query` added"]
+        );
+
+        let new = SCHEMA.replace("union SearchResult = User", "union SearchResult = Query");
+        assert_eq!(
+            changes(&new),
+            vec![
+                "BREAKING SearchResult: member `User` removed",
+                "DANGEROUS SearchResult: member `Query` added",
+            ]
+        );
+    }
+
+    #[test]
+    fn directives() {
+        let new = SCHEMA.replace("directive @auth(role: String) on FIELD_DEFINITION\n", "");
+        assert_eq!(changes(&new), vec!["BREAKING @auth: directive removed"]);
+        assert_eq!(
+            changes_between(&new, SCHEMA),
+            vec!["SAFE @auth: directive added"]
+        );
+
+        let new = SCHEMA.replace(
+            "on FIELD_DEFINITION",
+            "on FIELD_DEFINITION | INPUT_FIELD_DEFINITION",
+        );
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE @auth: location `INPUT_FIELD_DEFINITION` added"]
+        );
+
+        let new = SCHEMA.replace("on FIELD_DEFINITION", "on OBJECT");
+        assert_eq!(
+            changes(&new),
+            vec![
+                "BREAKING @auth: location `FIELD_DEFINITION` removed",
+                "SAFE @auth: location `OBJECT` added",
+            ]
+        );
+
+        let new = SCHEMA.replace("@auth(role: String)", "@auth(role: String, scope: String!)");
+        assert_eq!(
+            changes(&new),
+            vec!["BREAKING @auth.scope: required argument added"]
+        );
+
+        // Built-in directives are left out
+        let new = format!(
+            "{SCHEMA}\ndirective @deprecated(reason: String = \"No longer supported\") on FIELD_DEFINITION | ENUM_VALUE\n"
+        );
+        assert!(changes(&new).is_empty());
+    }
+
+    #[test]
+    fn deprecations() {
+        // `User` comes first
+        let new = SCHEMA.replacen(
+            "  fullName: String\n",
+            "  fullName: String @deprecated(reason: \"Use name\")\n",
+            1,
+        );
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE User.fullName: deprecated: Use name"]
+        );
+
+        let old = new;
+        let new = SCHEMA.replacen(
+            "  fullName: String\n",
+            "  fullName: String @deprecated\n",
+            1,
+        );
+        assert_eq!(
+            changes_between(&old, &new),
+            vec!["SAFE User.fullName: deprecation reason changed from `Use name` to `No longer supported`"]
+        );
+        assert_eq!(
+            changes_between(&new, SCHEMA),
+            vec!["SAFE User.fullName: no longer deprecated"]
+        );
+
+        let new = SCHEMA.replace("  NAME\n", "  NAME @deprecated\n");
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE UserOrderField.NAME: deprecated: No longer supported"]
+        );
+
+        let new = SCHEMA.replace("users(first: Int,", "users(first: Int @deprecated,");
+        assert_eq!(
+            changes(&new),
+            vec!["SAFE Query.users.first: deprecated: No longer supported"]
+        );
+    }
+
+    #[test]
+    fn added_and_changed_types() {
+        let new = SCHEMA.replace("scalar UUID", "scalar UUID\n\nscalar DateTime");
+        assert_eq!(changes(&new), vec!["SAFE DateTime: type added"]);
+
+        let new = SCHEMA.replace("scalar UUID", "enum UUID {\n  NIL\n}");
+        assert_eq!(
+            changes(&new),
+            vec!["BREAKING UUID: kind changed from `scalar` to `enum`"]
+        );
+    }
+}
@@ -0,0 +1,33 @@
+pub mod diff;
+mod node;
+
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use node::NodeQuery;
+
+use crate::{
+    auth::resolver::{AuthMutation, AuthQuery},
+    health::resolver::HealthQuery,
+    meta::resolver::MetaQuery,
+    user::resolver::{UserMutation, UserQuery, UserSubscription},
+};
+
+#[derive(MergedObject, Default)]
+pub struct Query(MetaQuery, UserQuery, AuthQuery, HealthQuery, NodeQuery);
+
+#[derive(MergedObject, Default)]
+pub struct Mutation(UserMutation, AuthMutation);
+
+#[derive(MergedSubscription, Default)]
+pub struct Subscription(UserSubscription);
+
+pub type AppSchema = Schema<Query, Mutation, Subscription>;
+
+/// Starts building the schema without any data, limits or extensions.
+/// Enough to export the SDL, see `src/bin/export_schema.rs`.
+pub fn build() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
+}
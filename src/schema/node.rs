@@ -0,0 +1,68 @@
+use async_graphql::{
+    dataloader::DataLoader, Context, ErrorExtensions, FieldResult, Interface, Object, ID,
+};
+use uuid::Uuid;
+
+use crate::{
+    relay::from_global_id,
+    user::{
+        model::{User, USER_NODE_TYPE},
+        UserLoader,
+    },
+    Error,
+};
+
+/// An object with a globally unique, opaque id
+#[derive(Interface)]
+#[graphql(field(name = "id", type = "ID", desc = "ID of the object."))]
+pub enum Node {
+    User(User),
+}
+
+#[derive(Default)]
+pub struct NodeQuery;
+
+#[Object]
+impl NodeQuery {
+    /// Fetches an object given its id
+    pub async fn node(&self, ctx: &Context<'_>, id: ID) -> FieldResult<Option<Node>> {
+        let mut nodes = find_nodes(ctx, &[id]).await?;
+        Ok(nodes.pop().flatten())
+    }
+    /// Fetches a list of objects given their ids, in the same order
+    #[graphql(complexity = "ids.len().max(1) * child_complexity")]
+    pub async fn nodes(&self, ctx: &Context<'_>, ids: Vec<ID>) -> FieldResult<Vec<Option<Node>>> {
+        find_nodes(ctx, &ids).await
+    }
+}
+
+/// Loads the objects with one query per type.
+/// Ids of unknown types, or of objects that don't exist, resolve to `None`.
+async fn find_nodes(ctx: &Context<'_>, ids: &[ID]) -> FieldResult<Vec<Option<Node>>> {
+    let user_loader = ctx.data::<DataLoader<UserLoader>>()?;
+
+    let global_ids = ids
+        .iter()
+        .map(from_global_id)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| Error::from(err).extend())?;
+
+    let user_ids: Vec<Uuid> = global_ids
+        .iter()
+        .filter(|(type_name, _)| type_name == USER_NODE_TYPE)
+        .map(|(_, id)| *id)
+        .collect();
+    let users = user_loader
+        .load_many(user_ids)
+        .await
+        .map_err(|err| err.extend())?;
+
+    let nodes = global_ids
+        .into_iter()
+        .map(|(type_name, id)| match type_name.as_str() {
+            USER_NODE_TYPE => users.get(&id).cloned().map(|user| Node::User(user.into())),
+            _ => None,
+        })
+        .collect();
+    Ok(nodes)
+}
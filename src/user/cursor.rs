@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    relay::{
+        decode_cursor, encode_cursor,
+        keyset::{Keyset, SortValue},
+        Base64CursorError, Cursor,
+    },
+    user::{entities, scalar::Time, service::UserOrderField},
+};
+
+/// The value of the column the connection is ordered by
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "field", content = "value", rename_all = "snake_case")]
+pub enum SortKey {
+    Id,
+    CreatedAt(Time),
+    UpdatedAt(Time),
+    Name(String),
+}
+
+/// Users cursor. It encodes the sort key along with the id,
+/// so pagination stays stable under any ordering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserCursor {
+    pub key: SortKey,
+    pub id: Uuid,
+}
+
+impl UserCursor {
+    pub fn new(user: &entities::User, field: UserOrderField) -> Self {
+        let key = match field {
+            UserOrderField::Id => SortKey::Id,
+            UserOrderField::CreatedAt => SortKey::CreatedAt(user.created_at),
+            UserOrderField::UpdatedAt => SortKey::UpdatedAt(user.updated_at),
+            UserOrderField::Name => SortKey::Name(user.name.clone()),
+        };
+        Self { key, id: user.id }
+    }
+
+    /// The field this cursor was created for
+    pub fn field(&self) -> UserOrderField {
+        match self.key {
+            SortKey::Id => UserOrderField::Id,
+            SortKey::CreatedAt(_) => UserOrderField::CreatedAt,
+            SortKey::UpdatedAt(_) => UserOrderField::UpdatedAt,
+            SortKey::Name(_) => UserOrderField::Name,
+        }
+    }
+}
+
+impl Cursor for UserCursor {
+    fn encode(&self) -> String {
+        encode_cursor(self)
+    }
+
+    fn decode(s: &str) -> Result<Self, Base64CursorError> {
+        decode_cursor(s)
+    }
+}
+
+impl From<&UserCursor> for Keyset {
+    fn from(cursor: &UserCursor) -> Self {
+        let value = match &cursor.key {
+            SortKey::Id => None,
+            SortKey::CreatedAt(created_at) => Some(SortValue::Time(*created_at)),
+            SortKey::UpdatedAt(updated_at) => Some(SortValue::Time(*updated_at)),
+            SortKey::Name(name) => Some(SortValue::Text(name.clone())),
+        };
+        Self {
+            value,
+            id: cursor.id,
+        }
+    }
+}
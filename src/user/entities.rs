@@ -1,7 +1,12 @@
+use std::{fmt, str::FromStr};
+
 use chrono;
+use serde::{Deserialize, Serialize};
 use sqlx;
 
-#[derive(sqlx::FromRow, Debug, Clone)]
+use crate::errors::core::Error;
+
+#[derive(sqlx::FromRow, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: uuid::Uuid,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -9,4 +14,47 @@ pub struct User {
 
     pub name: String,
     pub full_name: Option<String>,
+    // Never leave the process, e.g: in events
+    #[serde(skip)]
+    pub password_hash: Option<String>,
+}
+
+// Without the password hash, so users can safely end up in the logs
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("name", &self.name)
+            .field("full_name", &self.full_name)
+            .finish_non_exhaustive()
+    }
+}
+
+const ROLE_ADMIN: &str = "admin";
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+}
+
+impl FromStr for Role {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Role, Error> {
+        match s {
+            ROLE_ADMIN => Ok(Role::Admin),
+            _ => Err(Error::Internal),
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Admin => write!(f, "{}", ROLE_ADMIN),
+        }
+    }
 }
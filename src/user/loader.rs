@@ -0,0 +1,46 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+use uuid::Uuid;
+
+use crate::{
+    errors::Error,
+    user::{entities::User, Service},
+};
+
+/// Batches user lookups made while resolving a query,
+/// by id or by name, into a single `= ANY($1)` query
+pub struct UserLoader {
+    user_service: Arc<Service>,
+}
+
+impl UserLoader {
+    pub fn new(user_service: Arc<Service>) -> Self {
+        Self { user_service }
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Loader<Uuid> for UserLoader {
+    type Value = User;
+    type Error = Error;
+
+    async fn load(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, User>, Error> {
+        let users = self.user_service.find_users_by_ids(ids).await?;
+        Ok(users.into_iter().map(|user| (user.id, user)).collect())
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Loader<String> for UserLoader {
+    type Value = User;
+    type Error = Error;
+
+    async fn load(&self, names: &[String]) -> Result<HashMap<String, User>, Error> {
+        let users = self.user_service.find_users_by_names(names).await?;
+        Ok(users
+            .into_iter()
+            .map(|user| (user.name.clone(), user))
+            .collect())
+    }
+}
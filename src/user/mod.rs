@@ -1,9 +1,15 @@
-mod entities;
-mod model;
+mod cursor;
+pub(crate) mod entities;
+mod loader;
+pub(crate) mod model;
 mod repository;
 mod scalar;
 mod service;
 
 // public
 pub mod resolver;
+pub use entities::Role;
+pub use loader::UserLoader;
+pub use repository::{MemoryRepository, PostgresRepository, UserRepository, UserTransaction};
+pub(crate) use service::CreateUserInput;
 pub use service::Service;
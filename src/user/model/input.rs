@@ -1,7 +1,9 @@
-use async_graphql::InputObject;
-use uuid::Uuid;
+use async_graphql::{Enum, InputObject, MaybeUndefined, ID};
 
-use crate::user::scalar::Id;
+use crate::{
+    relay::OrderDirection,
+    user::scalar::{Id, Time},
+};
 
 #[derive(InputObject)]
 pub struct CreateUserInput {
@@ -11,12 +13,49 @@ pub struct CreateUserInput {
 
 #[derive(InputObject)]
 pub struct UpdateUserInput {
-    pub id: Uuid,
-    pub name: String,
-    pub full_name: Option<String>,
+    /// Id of the user, see `User.uuid`. Exactly one of `id` and `nodeId` is required.
+    pub id: Option<Id>,
+    /// Global id of the user, see `User.id`
+    pub node_id: Option<ID>,
+    /// The new name. Kept when absent, it can't be null.
+    pub name: MaybeUndefined<String>,
+    /// The new full name. Kept when absent, cleared when null.
+    pub full_name: MaybeUndefined<String>,
 }
 
 #[derive(InputObject)]
 pub struct DeleteUserInput {
     pub user_id: Id,
 }
+
+#[derive(InputObject, Default)]
+pub struct UserFilter {
+    /// Users whose name contains the value, case insensitive.
+    pub name_contains: Option<String>,
+    /// Users whose name starts with the value.
+    pub name_prefix: Option<String>,
+    /// Users with, or without, a full name.
+    pub full_name_is_null: Option<bool>,
+    /// Users created after the time, exclusive.
+    pub created_after: Option<Time>,
+    /// Users created before the time, exclusive.
+    pub created_before: Option<Time>,
+}
+
+/// Properties by which user connections can be ordered.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum UserOrderField {
+    CreatedAt,
+    Name,
+    UpdatedAt,
+}
+
+/// Ordering options for user connections.
+#[derive(InputObject)]
+pub struct UserOrder {
+    /// The field to order users by.
+    pub field: UserOrderField,
+    /// The ordering direction.
+    #[graphql(default_with = "OrderDirection::Asc")]
+    pub direction: OrderDirection,
+}
@@ -1,21 +1,43 @@
 pub mod input;
 use std::sync::Arc;
 
-use async_graphql::{ComplexObject, Context, Result, SimpleObject};
-use sqlx::Row;
+use async_graphql::{ComplexObject, Context, ErrorExtensions, FieldResult, SimpleObject, ID};
 
 use crate::{
     context::ServerContext,
-    relay::Base64Cursor,
+    errors::core::Error,
+    relay::{from_global_id, to_global_id, Connection, Edge, PageInfo},
     user::{
         entities,
         scalar::{Id, Time},
-        service,
+        service::UserFilter,
     },
 };
 
+/// Type name of users in global ids
+pub const USER_NODE_TYPE: &str = "User";
+
+/// Returns the id of a user from its global id, rejecting the global ids of other types
+pub fn user_id(id: &ID) -> Result<Id, Error> {
+    match from_global_id(id)? {
+        (type_name, id) if type_name == USER_NODE_TYPE => Ok(id),
+        _ => Err(Error::InvalidNodeId),
+    }
+}
+
+/// Returns the id of a user given either its id, see `User.uuid`, or its global id
+pub fn user_id_of(id: Option<Id>, node_id: Option<&ID>) -> Result<Id, Error> {
+    match (id, node_id) {
+        (Some(id), None) => Ok(id),
+        (None, Some(node_id)) => user_id(node_id),
+        _ => Err(Error::UserIdArguments),
+    }
+}
+
 #[derive(Debug, SimpleObject)]
+#[graphql(complex)]
 pub struct User {
+    #[graphql(skip)]
     pub id: Id,
     pub created_at: Time,
 
@@ -35,6 +57,18 @@ impl From<entities::User> for User {
     }
 }
 
+#[ComplexObject]
+impl User {
+    /// Globally unique id, see the `node` query
+    async fn id(&self) -> ID {
+        to_global_id(USER_NODE_TYPE, self.id)
+    }
+    /// The id stored in the database, the `id` argument of `user`, `updateUser` and `deleteUser`
+    async fn uuid(&self) -> Id {
+        self.id
+    }
+}
+
 #[derive(Debug, SimpleObject)]
 pub struct UserEdge {
     // The item at the end of the edge.
@@ -43,13 +77,11 @@ pub struct UserEdge {
     pub cursor: String,
 }
 
-impl From<entities::User> for UserEdge {
-    fn from(user: entities::User) -> Self {
-        let cursor = Base64Cursor::new(user.id).encode();
-        let user_model = user.into();
+impl From<Edge<User>> for UserEdge {
+    fn from(edge: Edge<User>) -> Self {
         Self {
-            node: user_model,
-            cursor,
+            node: edge.node,
+            cursor: edge.cursor,
         }
     }
 }
@@ -59,72 +91,34 @@ impl From<entities::User> for UserEdge {
 pub struct UserConnection {
     // A list of edges.
     pub edges: Vec<UserEdge>,
-    //
-    // helper
-    //
-    #[graphql(skip)]
-    pub after: Option<String>,
-    #[graphql(skip)]
-    pub before: Option<String>,
-    #[graphql(skip)]
-    pub first: Option<i32>,
+    // Information to aid in pagination.
+    pub page_info: PageInfo,
+    // The filter of the page, to count the items of the connection.
     #[graphql(skip)]
-    pub last: Option<i32>,
+    pub filter: UserFilter,
 }
 
-#[ComplexObject]
 impl UserConnection {
-    // Information to aid in pagination.
-    async fn page_info(&self, ctx: &Context<'_>) -> Result<PageInfo> {
-        let server_ctx = ctx.data::<Arc<ServerContext>>()?;
-        let page_info = server_ctx
-            .user_service
-            .find_page_info(
-                self.first,
-                self.after.clone(),
-                self.last,
-                self.before.clone(),
-            )
-            .await?;
-        Ok(page_info.into())
-    }
-    // Identifies the total count of items in the connection.
-    async fn total_count(&self, ctx: &Context<'_>) -> Result<i64> {
-        let server_ctx = ctx.data::<Arc<ServerContext>>()?;
-        let db = &server_ctx.user_service.db;
-
-        let total_count_query = "select count(*) as exact_count from  user_";
-        let total_count = match sqlx::query(total_count_query).fetch_one(db).await {
-            Err(err) => {
-                log::error!("counting users: {}", &err);
-                return Err(err.into());
-                // None
-            }
-            Ok(row) => Ok(row.get(0)),
-        };
-        total_count
+    pub fn new(connection: Connection<User>, filter: UserFilter) -> Self {
+        Self {
+            edges: connection.edges.into_iter().map(Into::into).collect(),
+            page_info: connection.page_info,
+            filter,
+        }
     }
 }
 
-#[derive(Debug, SimpleObject)]
-pub struct PageInfo {
-    // When paginating forwards, the cursor to continue.
-    pub end_cursor: Option<String>,
-    // When paginating forwards, are there more items?
-    pub has_next_page: bool,
-    // When paginating backwards, the cursor to continue.
-    pub start_cursor: Option<String>,
-    // When paginating backwards, are there more items?
-    pub has_previous_page: bool,
-}
+#[ComplexObject]
+impl UserConnection {
+    // Identifies the total count of items in the connection.
+    // Counting is a separate query, only run when asked for.
+    async fn total_count(&self, ctx: &Context<'_>) -> FieldResult<i64> {
+        let server_ctx = ctx.data::<Arc<ServerContext>>()?;
 
-impl From<service::PageInfo> for PageInfo {
-    fn from(page_info: service::PageInfo) -> Self {
-        Self {
-            has_next_page: page_info.has_next_page,
-            has_previous_page: page_info.has_previous_page,
-            start_cursor: page_info.start_cursor,
-            end_cursor: page_info.end_cursor,
+        let result = server_ctx.user_service.count_users(&self.filter).await;
+        match result {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.extend()),
         }
     }
 }
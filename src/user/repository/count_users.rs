@@ -0,0 +1,26 @@
+use sqlx::{self, Row};
+
+use super::{find_all_users::users_query, PostgresRepository};
+use crate::{
+    db::Queryer,
+    errors::core::Error,
+    user::service::{UserFilter, UserOrder},
+};
+
+impl PostgresRepository {
+    pub async fn count_users<'c, C: Queryer<'c>>(
+        &self,
+        db: C,
+        filter: &UserFilter,
+    ) -> Result<i64, Error> {
+        let mut query = users_query(filter, UserOrder::default()).count();
+
+        match query.build().fetch_one(db).await {
+            Err(err) => {
+                log::error!("counting users: {}", &err);
+                Err(err.into())
+            }
+            Ok(row) => Ok(row.get(0)),
+        }
+    }
+}
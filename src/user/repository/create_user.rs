@@ -1,16 +1,20 @@
 use sqlx;
 
-use super::Repository;
-use crate::{db::Queryer, errors::core::Error, user::entities};
+use super::PostgresRepository;
+use crate::{
+    db::{self, Queryer},
+    errors::core::Error,
+    user::entities,
+};
 
-impl Repository {
+impl PostgresRepository {
     pub async fn create_user<'c, C: Queryer<'c>>(
         &self,
         db: C,
         user: &entities::User,
     ) -> Result<entities::User, Error> {
-        const QUERY: &str = "insert into user_ (id, created_at, updated_at, 
-                              name, full_name) values ($1, $2, $3, $4, $5) returning *";
+        const QUERY: &str = "insert into user_ (id, created_at, updated_at,
+                              name, full_name, password_hash) values ($1, $2, $3, $4, $5, $6) returning *";
 
         match sqlx::query_as::<_, entities::User>(QUERY)
             .bind(user.id)
@@ -19,9 +23,12 @@ impl Repository {
             //
             .bind(&user.name)
             .bind(&user.full_name)
+            .bind(&user.password_hash)
             .fetch_one(db)
             .await
         {
+            // `name` is the only unique column, besides the generated id
+            Err(err) if db::is_unique_violation(&err) => Err(Error::UsernameAlreadyExists),
             Err(err) => {
                 log::error!("inserting user: {}", &err);
                 Err(err.into())
@@ -1,10 +1,10 @@
 use sqlx;
 use uuid::Uuid;
 
-use super::Repository;
+use super::PostgresRepository;
 use crate::{db::Queryer, errors::core::Error, user::entities};
 
-impl Repository {
+impl PostgresRepository {
     pub async fn delete_user<'c, C: Queryer<'c>>(
         &self,
         db: C,
@@ -0,0 +1,28 @@
+use sqlx;
+
+use super::PostgresRepository;
+use crate::{db::Queryer, errors::core::Error, user::entities};
+
+impl PostgresRepository {
+    /// Locks the row until the end of the transaction, so concurrent updates don't overwrite each other
+    pub async fn find_user_by_id_for_update<'c, C: Queryer<'c>>(
+        &self,
+        db: C,
+        user_id: uuid::Uuid,
+    ) -> Result<entities::User, Error> {
+        const QUERY: &str = "SELECT * FROM user_ WHERE id = $1 FOR UPDATE";
+
+        match sqlx::query_as::<_, entities::User>(QUERY)
+            .bind(user_id)
+            .fetch_optional(db)
+            .await
+        {
+            Err(err) => {
+                log::error!("finding user: {}", &err);
+                Err(err.into())
+            }
+            Ok(None) => Err(Error::UserNotFound),
+            Ok(Some(res)) => Ok(res),
+        }
+    }
+}
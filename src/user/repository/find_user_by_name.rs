@@ -1,9 +1,9 @@
 use sqlx;
 
-use super::Repository;
+use super::PostgresRepository;
 use crate::{db::Queryer, errors::core::Error, user::entities};
 
-impl Repository {
+impl PostgresRepository {
     pub async fn find_user_by_name<'c, C: Queryer<'c>>(
         &self,
         db: C,
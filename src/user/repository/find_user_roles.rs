@@ -0,0 +1,33 @@
+use sqlx;
+use uuid::Uuid;
+
+use super::PostgresRepository;
+use crate::{
+    db::Queryer,
+    errors::core::Error,
+    user::entities::{self, Role},
+};
+
+impl PostgresRepository {
+    pub async fn find_user_roles<'c, C: Queryer<'c>>(
+        &self,
+        db: C,
+        user_id: Uuid,
+    ) -> Result<Vec<entities::Role>, Error> {
+        const QUERY: &str = "select role from user_role where user_id = $1 order by role";
+
+        let roles = match sqlx::query_scalar::<_, String>(QUERY)
+            .bind(user_id)
+            .fetch_all(db)
+            .await
+        {
+            Err(err) => {
+                log::error!("finding user roles: {}", &err);
+                return Err(err.into());
+            }
+            Ok(roles) => roles,
+        };
+
+        roles.iter().map(|role| role.parse::<Role>()).collect()
+    }
+}
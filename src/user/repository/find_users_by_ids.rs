@@ -0,0 +1,27 @@
+use sqlx;
+use uuid::Uuid;
+
+use super::PostgresRepository;
+use crate::{db::Queryer, errors::core::Error, user::entities};
+
+impl PostgresRepository {
+    pub async fn find_users_by_ids<'c, C: Queryer<'c>>(
+        &self,
+        db: C,
+        ids: &[Uuid],
+    ) -> Result<Vec<entities::User>, Error> {
+        const QUERY: &str = "SELECT * FROM user_ WHERE id = ANY($1)";
+
+        match sqlx::query_as::<_, entities::User>(QUERY)
+            .bind(ids)
+            .fetch_all(db)
+            .await
+        {
+            Err(err) => {
+                log::error!("finding users by ids: {}", &err);
+                Err(err.into())
+            }
+            Ok(res) => Ok(res),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use sqlx;
+
+use super::PostgresRepository;
+use crate::{db::Queryer, errors::core::Error, user::entities};
+
+impl PostgresRepository {
+    pub async fn find_users_by_names<'c, C: Queryer<'c>>(
+        &self,
+        db: C,
+        names: &[String],
+    ) -> Result<Vec<entities::User>, Error> {
+        const QUERY: &str = "SELECT * FROM user_ WHERE name = ANY($1)";
+
+        match sqlx::query_as::<_, entities::User>(QUERY)
+            .bind(names)
+            .fetch_all(db)
+            .await
+        {
+            Err(err) => {
+                log::error!("finding users by names: {}", &err);
+                Err(err.into())
+            }
+            Ok(res) => Ok(res),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use sqlx::{self, Row};
+
+use super::{find_all_users::users_query, PostgresRepository};
+use crate::{
+    db::Queryer,
+    errors::core::Error,
+    relay::keyset::Keyset,
+    user::{
+        cursor::UserCursor,
+        service::{UserFilter, UserOrder},
+    },
+};
+
+impl PostgresRepository {
+    /// returns true if any user comes after the cursor, the cursor user included
+    pub async fn has_users_after<'c, C: Queryer<'c>>(
+        &self,
+        db: C,
+        filter: &UserFilter,
+        order: UserOrder,
+        cursor: &UserCursor,
+    ) -> Result<bool, Error> {
+        let keyset: Keyset = cursor.into();
+        let mut query = users_query(filter, order).exists_after(&keyset);
+
+        match query.build().fetch_one(db).await {
+            Err(err) => {
+                log::error!("finding users after cursor: {}", &err);
+                Err(err.into())
+            }
+            Ok(row) => Ok(row.get(0)),
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
+
+use async_graphql::async_trait::async_trait;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use uuid::Uuid;
+
+use super::{UserRepository, UserTransaction};
+use crate::{
+    errors::core::Error,
+    relay::{
+        keyset::{Keyset, SortValue},
+        OrderDirection,
+    },
+    user::{
+        cursor::UserCursor,
+        entities::{Role, User},
+        service::{UserFilter, UserOrder},
+    },
+};
+
+/// Keeps the users in memory, to run the services without a database, e.g: in tests.
+/// A transaction locks the whole store, so transactions run one at a time.
+/// Names are compared byte by byte, Postgres might order them differently depending on its collation.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRepository {
+    store: Arc<Mutex<Store>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Store {
+    users: HashMap<Uuid, User>,
+    roles: HashMap<Uuid, Vec<Role>>,
+}
+
+impl MemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// There is no API to grant roles yet, see the `user_role` table
+    pub async fn grant_admin(&self, user_id: Uuid) {
+        let mut store = self.store.lock().await;
+        let roles = store.roles.entry(user_id).or_default();
+        if !roles.contains(&Role::Admin) {
+            roles.push(Role::Admin);
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for MemoryRepository {
+    async fn begin(&self) -> Result<Box<dyn UserTransaction>, Error> {
+        let store = Arc::clone(&self.store).lock_owned().await;
+        let snapshot = Some(store.clone());
+        Ok(Box::new(MemoryTransaction { store, snapshot }))
+    }
+
+    async fn find_user_by_id(&self, user_id: Uuid) -> Result<User, Error> {
+        self.store.lock().await.find_user_by_id(user_id)
+    }
+
+    async fn find_user_by_name(&self, name: &str) -> Result<User, Error> {
+        self.store.lock().await.find_user_by_name(name)
+    }
+
+    async fn find_user_roles(&self, user_id: Uuid) -> Result<Vec<Role>, Error> {
+        let store = self.store.lock().await;
+        Ok(store.roles.get(&user_id).cloned().unwrap_or_default())
+    }
+
+    async fn find_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>, Error> {
+        let store = self.store.lock().await;
+        Ok(ids
+            .iter()
+            .filter_map(|id| store.users.get(id))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_users_by_names(&self, names: &[String]) -> Result<Vec<User>, Error> {
+        let store = self.store.lock().await;
+        Ok(store
+            .users
+            .values()
+            .filter(|user| names.contains(&user.name))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_all_users(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        first: Option<i32>,
+        after: Option<&UserCursor>,
+        last: Option<i32>,
+        before: Option<&UserCursor>,
+    ) -> Result<Vec<User>, Error> {
+        let after: Option<Keyset> = after.map(Into::into);
+        let before: Option<Keyset> = before.map(Into::into);
+
+        let users: Vec<User> = self
+            .store
+            .lock()
+            .await
+            .users(filter, order)
+            .into_iter()
+            .filter(|user| match &after {
+                Some(after) => compare(user, after, order) == Ordering::Greater,
+                None => true,
+            })
+            .filter(|user| match &before {
+                Some(before) => compare(user, before, order) == Ordering::Less,
+                None => true,
+            })
+            .collect();
+
+        // Same as `KeysetQuery::page`, one extra user tells whether there are more pages
+        let users = match (first, last) {
+            (None, Some(last)) => {
+                let limit = usize::try_from(last)? + 1;
+                users[users.len().saturating_sub(limit)..].to_vec()
+            }
+            (first, _) => {
+                let limit = usize::try_from(first.unwrap_or_default())? + 1;
+                users.into_iter().take(limit).collect()
+            }
+        };
+
+        Ok(users)
+    }
+
+    async fn count_users(&self, filter: &UserFilter) -> Result<i64, Error> {
+        let store = self.store.lock().await;
+        Ok(i64::try_from(
+            store.users(filter, UserOrder::default()).len(),
+        )?)
+    }
+
+    async fn has_users_before(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        cursor: &UserCursor,
+    ) -> Result<bool, Error> {
+        let keyset: Keyset = cursor.into();
+        let store = self.store.lock().await;
+        Ok(store
+            .users(filter, order)
+            .iter()
+            .any(|user| compare(user, &keyset, order) != Ordering::Greater))
+    }
+
+    async fn has_users_after(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        cursor: &UserCursor,
+    ) -> Result<bool, Error> {
+        let keyset: Keyset = cursor.into();
+        let store = self.store.lock().await;
+        Ok(store
+            .users(filter, order)
+            .iter()
+            .any(|user| compare(user, &keyset, order) != Ordering::Less))
+    }
+
+    async fn delete_user(&self, user_id: Uuid) -> Result<User, Error> {
+        let mut store = self.store.lock().await;
+        // Same as `on delete cascade`
+        store.roles.remove(&user_id);
+        store.users.remove(&user_id).ok_or(Error::UserNotFound)
+    }
+}
+
+impl Store {
+    fn find_user_by_id(&self, user_id: Uuid) -> Result<User, Error> {
+        self.users.get(&user_id).cloned().ok_or(Error::UserNotFound)
+    }
+
+    fn find_user_by_name(&self, name: &str) -> Result<User, Error> {
+        self.users
+            .values()
+            .find(|user| user.name == name)
+            .cloned()
+            .ok_or(Error::UserNotFound)
+    }
+
+    /// Same as the `user_.name` unique constraint
+    fn check_unique_name(&self, user: &User) -> Result<(), Error> {
+        let taken = self
+            .users
+            .values()
+            .any(|other| other.name == user.name && other.id != user.id);
+        if taken {
+            return Err(Error::UsernameAlreadyExists);
+        }
+        Ok(())
+    }
+
+    /// Users matching the filter, in order
+    fn users(&self, filter: &UserFilter, order: UserOrder) -> Vec<User> {
+        let mut users: Vec<User> = self
+            .users
+            .values()
+            .filter(|user| matches(user, filter))
+            .cloned()
+            .collect();
+        users.sort_by(|a, b| compare(a, &keyset(b, order), order));
+        users
+    }
+}
+
+/// See `impl Conditions for UserFilter`
+fn matches(user: &User, filter: &UserFilter) -> bool {
+    if let Some(name_contains) = &filter.name_contains {
+        if !user
+            .name
+            .to_lowercase()
+            .contains(&name_contains.to_lowercase())
+        {
+            return false;
+        }
+    }
+    if let Some(name_prefix) = &filter.name_prefix {
+        if !user.name.starts_with(name_prefix.as_str()) {
+            return false;
+        }
+    }
+    if let Some(full_name_is_null) = filter.full_name_is_null {
+        if user.full_name.is_none() != full_name_is_null {
+            return false;
+        }
+    }
+    if let Some(created_after) = filter.created_after {
+        if user.created_at <= created_after {
+            return false;
+        }
+    }
+    if let Some(created_before) = filter.created_before {
+        if user.created_at >= created_before {
+            return false;
+        }
+    }
+    true
+}
+
+fn keyset(user: &User, order: UserOrder) -> Keyset {
+    (&UserCursor::new(user, order.field)).into()
+}
+
+/// Where the user is relative to the keyset, in the order of the connection
+fn compare(user: &User, keyset: &Keyset, order: UserOrder) -> Ordering {
+    let position = self::keyset(user, order);
+    let value = match (&position.value, &keyset.value) {
+        (Some(SortValue::Time(a)), Some(SortValue::Time(b))) => a.cmp(b),
+        (Some(SortValue::Text(a)), Some(SortValue::Text(b))) => a.cmp(b),
+        _ => Ordering::Equal,
+    };
+    let ordering = value.then(position.id.cmp(&keyset.id));
+
+    match order.direction {
+        OrderDirection::Asc => ordering,
+        OrderDirection::Desc => ordering.reverse(),
+    }
+}
+
+struct MemoryTransaction {
+    store: OwnedMutexGuard<Store>,
+    /// Restored unless committed
+    snapshot: Option<Store>,
+}
+
+#[async_trait]
+impl UserTransaction for MemoryTransaction {
+    async fn find_user_by_id_for_update(&mut self, user_id: Uuid) -> Result<User, Error> {
+        self.store.find_user_by_id(user_id)
+    }
+
+    async fn find_user_by_name(&mut self, name: &str) -> Result<User, Error> {
+        self.store.find_user_by_name(name)
+    }
+
+    async fn create_user(&mut self, user: &User) -> Result<User, Error> {
+        self.store.check_unique_name(user)?;
+        self.store.users.insert(user.id, user.clone());
+        Ok(user.clone())
+    }
+
+    async fn update_user(&mut self, user: &User) -> Result<User, Error> {
+        self.store.check_unique_name(user)?;
+        let stored = self
+            .store
+            .users
+            .get_mut(&user.id)
+            .ok_or(Error::UserNotFound)?;
+        stored.updated_at = user.updated_at;
+        stored.name = user.name.clone();
+        stored.full_name = user.full_name.clone();
+        Ok(stored.clone())
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<(), Error> {
+        self.snapshot = None;
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), Error> {
+        // See `Drop`
+        Ok(())
+    }
+}
+
+impl Drop for MemoryTransaction {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            *self.store = snapshot;
+        }
+    }
+}
@@ -1,21 +1,118 @@
+mod count_users;
 mod create_user;
 mod delete_user;
 mod find_all_users;
 mod find_user_by_id;
+mod find_user_by_id_for_update;
 mod find_user_by_name;
+mod find_user_roles;
+mod find_users_by_ids;
+mod find_users_by_names;
+mod has_users_after;
+mod has_users_before;
+mod memory;
+mod postgres;
 mod update_user;
 
-#[derive(Debug, Clone)]
-pub struct Repository {}
+use std::fmt::Debug;
+
+use async_graphql::async_trait::async_trait;
+pub use memory::MemoryRepository;
+use uuid::Uuid;
+
+use crate::{
+    db::DB,
+    errors::core::Error,
+    user::{
+        cursor::UserCursor,
+        entities::{Role, User},
+        service::{UserFilter, UserOrder},
+    },
+};
+
+/// Storage of the users.
+/// Writes that must be atomic go through a `UserTransaction`, see `begin`.
+#[async_trait]
+pub trait UserRepository: Debug + Send + Sync {
+    async fn begin(&self) -> Result<Box<dyn UserTransaction>, Error>;
+
+    async fn find_user_by_id(&self, user_id: Uuid) -> Result<User, Error>;
+    async fn find_user_by_name(&self, name: &str) -> Result<User, Error>;
+    async fn find_user_roles(&self, user_id: Uuid) -> Result<Vec<Role>, Error>;
+    async fn find_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>, Error>;
+    async fn find_users_by_names(&self, names: &[String]) -> Result<Vec<User>, Error>;
+    /// Fetches one user more than `first` or `last`, see `KeysetQuery::page`
+    async fn find_all_users(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        first: Option<i32>,
+        after: Option<&UserCursor>,
+        last: Option<i32>,
+        before: Option<&UserCursor>,
+    ) -> Result<Vec<User>, Error>;
+    async fn count_users(&self, filter: &UserFilter) -> Result<i64, Error>;
+    /// returns true if any user comes before the cursor, the cursor user included
+    async fn has_users_before(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        cursor: &UserCursor,
+    ) -> Result<bool, Error>;
+    /// returns true if any user comes after the cursor, the cursor user included
+    async fn has_users_after(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        cursor: &UserCursor,
+    ) -> Result<bool, Error>;
+    async fn delete_user(&self, user_id: Uuid) -> Result<User, Error>;
+}
 
-impl Repository {
-    pub fn new() -> Repository {
-        Repository {}
+/// A unit of work on a `UserRepository`.
+/// Dropping it without `commit` rolls it back.
+#[async_trait]
+pub trait UserTransaction: Send {
+    /// Locks the user until the end of the transaction
+    async fn find_user_by_id_for_update(&mut self, user_id: Uuid) -> Result<User, Error>;
+    async fn find_user_by_name(&mut self, name: &str) -> Result<User, Error>;
+    /// Fails with `UsernameAlreadyExists` if the name is taken
+    async fn create_user(&mut self, user: &User) -> Result<User, Error>;
+    /// Fails with `UsernameAlreadyExists` if the name is taken by another user
+    async fn update_user(&mut self, user: &User) -> Result<User, Error>;
+
+    async fn commit(self: Box<Self>) -> Result<(), Error>;
+    async fn rollback(self: Box<Self>) -> Result<(), Error>;
+}
+
+impl dyn UserTransaction {
+    /// Commits when the work succeeded, rolls back otherwise.
+    /// The error of the work wins over a rollback error.
+    pub async fn finish<T>(
+        self: Box<Self>,
+        result: Result<T, crate::Error>,
+    ) -> Result<T, crate::Error> {
+        match result {
+            Ok(value) => {
+                self.commit().await?;
+                Ok(value)
+            }
+            Err(err) => {
+                let _ = self.rollback().await;
+                Err(err)
+            }
+        }
     }
 }
 
-impl Default for Repository {
-    fn default() -> Self {
-        Self::new()
+/// The users stored in Postgres, see the `user_` and `user_role` tables
+#[derive(Debug, Clone)]
+pub struct PostgresRepository {
+    db: DB,
+}
+
+impl PostgresRepository {
+    pub fn new(db: DB) -> Self {
+        Self { db }
     }
 }
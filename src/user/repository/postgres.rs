@@ -0,0 +1,123 @@
+use async_graphql::async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{PostgresRepository, UserRepository, UserTransaction};
+use crate::{
+    db::UnitOfWork,
+    errors::core::Error,
+    user::{
+        cursor::UserCursor,
+        entities::{Role, User},
+        service::{UserFilter, UserOrder},
+    },
+};
+
+// The queries live in one file per method, as inherent methods taking any `Queryer`.
+// Inherent methods take precedence, so `Self::find_user_by_id(self, ..)` calls the query.
+
+#[async_trait]
+impl UserRepository for PostgresRepository {
+    async fn begin(&self) -> Result<Box<dyn UserTransaction>, Error> {
+        // Already logged
+        let uow = UnitOfWork::begin(&self.db)
+            .await
+            .map_err(|_| Error::Internal)?;
+        Ok(Box::new(PostgresTransaction {
+            repo: self.clone(),
+            uow,
+        }))
+    }
+
+    async fn find_user_by_id(&self, user_id: Uuid) -> Result<User, Error> {
+        Self::find_user_by_id(self, &self.db, user_id).await
+    }
+
+    async fn find_user_by_name(&self, name: &str) -> Result<User, Error> {
+        Self::find_user_by_name(self, &self.db, name).await
+    }
+
+    async fn find_user_roles(&self, user_id: Uuid) -> Result<Vec<Role>, Error> {
+        Self::find_user_roles(self, &self.db, user_id).await
+    }
+
+    async fn find_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>, Error> {
+        Self::find_users_by_ids(self, &self.db, ids).await
+    }
+
+    async fn find_users_by_names(&self, names: &[String]) -> Result<Vec<User>, Error> {
+        Self::find_users_by_names(self, &self.db, names).await
+    }
+
+    async fn find_all_users(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        first: Option<i32>,
+        after: Option<&UserCursor>,
+        last: Option<i32>,
+        before: Option<&UserCursor>,
+    ) -> Result<Vec<User>, Error> {
+        Self::find_all_users(self, &self.db, filter, order, first, after, last, before).await
+    }
+
+    async fn count_users(&self, filter: &UserFilter) -> Result<i64, Error> {
+        Self::count_users(self, &self.db, filter).await
+    }
+
+    async fn has_users_before(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        cursor: &UserCursor,
+    ) -> Result<bool, Error> {
+        Self::has_users_before(self, &self.db, filter, order, cursor).await
+    }
+
+    async fn has_users_after(
+        &self,
+        filter: &UserFilter,
+        order: UserOrder,
+        cursor: &UserCursor,
+    ) -> Result<bool, Error> {
+        Self::has_users_after(self, &self.db, filter, order, cursor).await
+    }
+
+    async fn delete_user(&self, user_id: Uuid) -> Result<User, Error> {
+        Self::delete_user(self, &self.db, user_id).await
+    }
+}
+
+struct PostgresTransaction {
+    repo: PostgresRepository,
+    uow: UnitOfWork,
+}
+
+#[async_trait]
+impl UserTransaction for PostgresTransaction {
+    async fn find_user_by_id_for_update(&mut self, user_id: Uuid) -> Result<User, Error> {
+        self.repo
+            .find_user_by_id_for_update(self.uow.tx(), user_id)
+            .await
+    }
+
+    async fn find_user_by_name(&mut self, name: &str) -> Result<User, Error> {
+        self.repo.find_user_by_name(self.uow.tx(), name).await
+    }
+
+    async fn create_user(&mut self, user: &User) -> Result<User, Error> {
+        self.repo.create_user(self.uow.tx(), user).await
+    }
+
+    async fn update_user(&mut self, user: &User) -> Result<User, Error> {
+        self.repo.update_user(self.uow.tx(), user).await
+    }
+
+    // Both already logged
+    async fn commit(self: Box<Self>) -> Result<(), Error> {
+        self.uow.commit().await.map_err(|_| Error::Internal)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), Error> {
+        self.uow.rollback().await.map_err(|_| Error::Internal)
+    }
+}
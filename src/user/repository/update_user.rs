@@ -1,9 +1,9 @@
 use sqlx;
 
-use super::Repository;
+use super::PostgresRepository;
 use crate::{db, errors::core::Error, user::entities};
 
-impl Repository {
+impl PostgresRepository {
     pub async fn update_user<'c, C: db::Queryer<'c>>(
         &self,
         db: C,
@@ -12,7 +12,7 @@ impl Repository {
         const QUERY: &str = "update user_ set
               updated_at = $2,
               name = $3,
-              full_name = COALESCE($4, full_name)
+              full_name = $4
            where id = $1 returning *";
 
         match sqlx::query_as::<_, entities::User>(QUERY)
@@ -21,14 +21,17 @@ impl Repository {
             //
             .bind(&user.name)
             .bind(&user.full_name)
-            .fetch_one(db)
+            .fetch_optional(db)
             .await
         {
+            // `name` is the only unique column, besides the generated id
+            Err(err) if db::is_unique_violation(&err) => Err(Error::UsernameAlreadyExists),
             Err(err) => {
                 log::error!("updating user: {}", &err);
                 Err(err.into())
             }
-            Ok(user) => Ok(user),
+            Ok(None) => Err(Error::UserNotFound),
+            Ok(Some(user)) => Ok(user),
         }
     }
 }
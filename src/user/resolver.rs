@@ -1,10 +1,21 @@
 use std::sync::Arc;
 
-use async_graphql::{Context, Error, FieldResult, Object};
+use async_graphql::{Context, ErrorExtensions, FieldResult, Object, Subscription, ID};
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
 use uuid::Uuid;
 
-use super::model::{input, User, UserConnection};
-use crate::{context::ServerContext, user::scalar::Id};
+use super::model::{input, user_id_of, User, UserConnection};
+use crate::{
+    auth::guard::{OwnerOrAdminGuard, RoleGuard},
+    context::ServerContext,
+    events::Event,
+    relay::connection_complexity,
+    user::{
+        entities::Role,
+        service::{UserFilter, UserOrder},
+    },
+    Error,
+};
 
 #[derive(Default)]
 pub struct UserQuery;
@@ -12,8 +23,12 @@ pub struct UserQuery;
 #[derive(Default)]
 pub struct UserMutation;
 
+#[derive(Default)]
+pub struct UserSubscription;
+
 #[Object]
 impl UserQuery {
+    #[graphql(complexity = "connection_complexity(first, last, child_complexity)")]
     pub async fn users(
         &self,
         ctx: &Context<'_>,
@@ -21,37 +36,44 @@ impl UserQuery {
         after: Option<String>,
         last: Option<i32>,
         before: Option<String>,
+        filter: Option<input::UserFilter>,
+        order_by: Option<input::UserOrder>,
     ) -> FieldResult<UserConnection> {
         let server_ctx = ctx.data::<Arc<ServerContext>>()?;
-        let edges = server_ctx
-            .user_service
-            .find_users(first, after.clone(), last, before.clone())
-            .await?;
+        let filter: UserFilter = filter.map(Into::into).unwrap_or_default();
+        let order: UserOrder = order_by.map(Into::into).unwrap_or_default();
 
-        let user_connection = UserConnection {
-            edges,
-            //
-            after,
-            before,
-            first,
-            last,
-        };
-
-        Ok(user_connection)
+        let result = server_ctx
+            .user_service
+            .find_users(&filter, order, first, after, last, before)
+            .await;
+        match result {
+            Ok(res) => Ok(UserConnection::new(res, filter)),
+            Err(err) => Err(err.extend()),
+        }
     }
-    pub async fn user(&self, ctx: &Context<'_>, id: Uuid) -> FieldResult<User> {
+    /// Fetches a user given either its id, see `User.uuid`, or its global id, see `User.id`
+    pub async fn user(
+        &self,
+        ctx: &Context<'_>,
+        id: Option<Uuid>,
+        node_id: Option<ID>,
+    ) -> FieldResult<User> {
         let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+        let id = user_id_of(id, node_id.as_ref()).map_err(|err| Error::from(err).extend())?;
 
         let result = server_ctx.user_service.find_user(id).await;
         match result {
             Ok(res) => Ok(res.into()),
-            Err(err) => Err(Error::new(err.to_string())),
+            Err(err) => Err(err.extend()),
         }
     }
 }
 
 #[Object]
 impl UserMutation {
+    /// Requires the caller to be an admin, users sign up with `register`
+    #[graphql(guard = "RoleGuard::new(Role::Admin)")]
     pub async fn create_user(
         &self,
         ctx: &Context<'_>,
@@ -62,29 +84,85 @@ impl UserMutation {
         let result = server_ctx.user_service.create_user(input.into()).await;
         match result {
             Ok(res) => Ok(res.into()),
-            Err(err) => Err(Error::new(err.to_string())),
+            Err(err) => Err(err.extend()),
         }
     }
+    /// Requires the caller to be the user being updated, or an admin
+    #[graphql(guard = "OwnerOrAdminGuard::new(input.id, &input.node_id)")]
     pub async fn update_user(
         &self,
         ctx: &Context<'_>,
         input: input::UpdateUserInput,
     ) -> FieldResult<User> {
         let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+        let input = input.try_into().map_err(|err: Error| err.extend())?;
 
-        let result = server_ctx.user_service.update_user(input.into()).await;
+        let result = server_ctx.user_service.update_user(input).await;
         match result {
             Ok(res) => Ok(res.into()),
-            Err(err) => Err(Error::new(err.to_string())),
+            Err(err) => Err(err.extend()),
         }
     }
-    pub async fn delete_user(&self, ctx: &Context<'_>, id: Id) -> FieldResult<User> {
+    /// Requires the caller to be the user being deleted, or an admin
+    #[graphql(guard = "OwnerOrAdminGuard::new(id, node_id)")]
+    pub async fn delete_user(
+        &self,
+        ctx: &Context<'_>,
+        id: Option<Uuid>,
+        node_id: Option<ID>,
+    ) -> FieldResult<User> {
         let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+        let id = user_id_of(id, node_id.as_ref()).map_err(|err| Error::from(err).extend())?;
 
         let result = server_ctx.user_service.delete_user(id).await;
         match result {
             Ok(res) => Ok(res.into()),
-            Err(err) => Err(Error::new(err.to_string())),
+            Err(err) => Err(err.extend()),
         }
     }
 }
+
+#[Subscription]
+impl UserSubscription {
+    /// Requires the caller to be an admin
+    #[graphql(guard = "RoleGuard::new(Role::Admin)")]
+    pub async fn user_created(&self, ctx: &Context<'_>) -> FieldResult<impl Stream<Item = User>> {
+        user_events(ctx, |event| match event {
+            Event::UserCreated(user) => Some(user.into()),
+            _ => None,
+        })
+    }
+    /// Requires the caller to be an admin
+    #[graphql(guard = "RoleGuard::new(Role::Admin)")]
+    pub async fn user_updated(&self, ctx: &Context<'_>) -> FieldResult<impl Stream<Item = User>> {
+        user_events(ctx, |event| match event {
+            Event::UserUpdated(user) => Some(user.into()),
+            _ => None,
+        })
+    }
+    /// Requires the caller to be an admin
+    #[graphql(guard = "RoleGuard::new(Role::Admin)")]
+    pub async fn user_deleted(&self, ctx: &Context<'_>) -> FieldResult<impl Stream<Item = User>> {
+        user_events(ctx, |event| match event {
+            Event::UserDeleted(user) => Some(user.into()),
+            _ => None,
+        })
+    }
+}
+
+fn user_events<F>(ctx: &Context<'_>, select: F) -> FieldResult<impl Stream<Item = User>>
+where
+    F: Fn(Event) -> Option<User> + Send + 'static,
+{
+    let server_ctx = ctx.data::<Arc<ServerContext>>()?;
+    let receiver = server_ctx.user_service.subscribe();
+
+    let stream = BroadcastStream::new(receiver).filter_map(move |event| match event {
+        Ok(event) => select(event),
+        Err(err) => {
+            log::warn!("user subscription: {}", err);
+            None
+        }
+    });
+    Ok(stream)
+}
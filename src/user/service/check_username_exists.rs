@@ -1,14 +1,14 @@
 use super::Service;
-use crate::{db::Queryer, errors::core::Error};
+use crate::{errors::core::Error, user::repository::UserTransaction};
 
 impl Service {
     /// returns true if a username exists. false otherwise
-    pub async fn check_username_exists<'c, C: Queryer<'c>>(
+    pub async fn check_username_exists(
         &self,
-        db: C,
+        tx: &mut dyn UserTransaction,
         name: &str,
     ) -> Result<bool, crate::Error> {
-        let find_existing_username_res = self.repo.find_user_by_name(db, name).await;
+        let find_existing_username_res = tx.find_user_by_name(name).await;
         match find_existing_username_res {
             Ok(_) => Ok(true),
             Err(Error::UserNotFound) => Ok(false),
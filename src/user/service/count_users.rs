@@ -0,0 +1,9 @@
+use super::{Service, UserFilter};
+use crate::errors::Error;
+
+impl Service {
+    pub async fn count_users(&self, filter: &UserFilter) -> Result<i64, Error> {
+        let total_count = self.repo.count_users(filter).await?;
+        Ok(total_count)
+    }
+}
@@ -2,11 +2,31 @@ use chrono::Utc;
 use ulid::Ulid;
 
 use super::{CreateUserInput, Service};
-use crate::{errors, user::entities::User};
+use crate::{
+    errors,
+    events::Event,
+    user::{entities::User, repository::UserTransaction},
+};
 
 impl Service {
     pub async fn create_user(&self, input: CreateUserInput) -> Result<User, errors::Error> {
-        let username_exists = self.check_username_exists(&self.db, &input.name).await?;
+        let mut tx = self.begin().await?;
+        let result = self.create_user_in(tx.as_mut(), input).await;
+        let user = tx.finish(result).await?;
+
+        // Only once committed, subscribers might look the user up
+        self.events.publish(Event::UserCreated(user.clone())).await;
+
+        Ok(user)
+    }
+
+    async fn create_user_in(
+        &self,
+        tx: &mut dyn UserTransaction,
+        input: CreateUserInput,
+    ) -> Result<User, errors::Error> {
+        // Concurrent requests can still pass this check, the unique constraint settles them
+        let username_exists = self.check_username_exists(tx, &input.name).await?;
         if username_exists {
             return Err(errors::core::Error::UsernameAlreadyExists.into());
         }
@@ -15,11 +35,12 @@ impl Service {
             id: Ulid::new().into(),
             name: input.name,
             full_name: input.full_name,
+            password_hash: input.password_hash,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
 
-        let user = self.repo.create_user(&self.db, &user_input).await?;
+        let user = tx.create_user(&user_input).await?;
 
         Ok(user)
     }
@@ -1,11 +1,12 @@
 use uuid::Uuid;
 
 use super::Service;
-use crate::{errors::Error, user::entities::User};
+use crate::{errors::Error, events::Event, user::entities::User};
 
 impl Service {
     pub async fn delete_user(&self, user_id: Uuid) -> Result<User, Error> {
-        let user = self.repo.delete_user(&self.db, user_id).await?;
+        let user = self.repo.delete_user(user_id).await?;
+        self.events.publish(Event::UserDeleted(user.clone())).await;
 
         Ok(user)
     }
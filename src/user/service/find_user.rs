@@ -5,7 +5,7 @@ use crate::{errors::Error, user::entities::User};
 
 impl Service {
     pub async fn find_user(&self, id: Uuid) -> Result<User, Error> {
-        let user = self.repo.find_user_by_id(&self.db, id).await?;
+        let user = self.repo.find_user_by_id(id).await?;
 
         Ok(user)
     }
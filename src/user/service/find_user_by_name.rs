@@ -0,0 +1,10 @@
+use super::Service;
+use crate::{errors::Error, user::entities::User};
+
+impl Service {
+    pub async fn find_user_by_name(&self, name: &str) -> Result<User, Error> {
+        let user = self.repo.find_user_by_name(name).await?;
+
+        Ok(user)
+    }
+}
@@ -0,0 +1,12 @@
+use uuid::Uuid;
+
+use super::Service;
+use crate::{errors::Error, user::entities::Role};
+
+impl Service {
+    pub async fn find_user_roles(&self, user_id: Uuid) -> Result<Vec<Role>, Error> {
+        let roles = self.repo.find_user_roles(user_id).await?;
+
+        Ok(roles)
+    }
+}
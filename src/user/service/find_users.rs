@@ -1,47 +1,76 @@
-use super::{PageInfo, Service};
+use super::{Service, UserFilter, UserOrder};
 use crate::{
     errors::Error,
-    relay::validation::{convert_params, validate_params},
-    user::model::UserEdge,
+    relay::{
+        validation::{convert_params, validate_params},
+        Base64CursorError, Connection,
+    },
+    user::{cursor::UserCursor, model::User},
 };
 
 impl Service {
     pub async fn find_users(
         &self,
+        filter: &UserFilter,
+        order: UserOrder,
         first: Option<i32>,
         after: Option<String>,
         last: Option<i32>,
         before: Option<String>,
-    ) -> Result<Vec<UserEdge>, Error> {
-        validate_params(first, last)?;
-        let (after_uuid, before_uuid) = convert_params(after, before)?;
+    ) -> Result<Connection<User>, Error> {
+        let (first, last) = validate_params(first, last, &self.pagination)?;
+        let (after_cursor, before_cursor) = self.convert_cursors(order, after, before)?;
 
         let users = self
             .repo
-            .find_all_users(&self.db, first, after_uuid, last, before_uuid)
+            .find_all_users(
+                filter,
+                order,
+                first,
+                after_cursor.as_ref(),
+                last,
+                before_cursor.as_ref(),
+            )
             .await?;
 
-        let user_edges: Vec<UserEdge> = users.into_iter().map(|user| user.into()).collect();
-        Ok(user_edges)
+        let mut connection = Connection::from_rows(users, first, last, |user| {
+            UserCursor::new(user, order.field)
+        });
+
+        // Relay spec, `HasPreviousPage` and `HasNextPage`: https://relay.dev/graphql/connections.htm
+        if let Some(after) = &after_cursor {
+            if !connection.page_info.has_previous_page {
+                connection.page_info.has_previous_page =
+                    self.repo.has_users_before(filter, order, after).await?;
+            }
+        }
+        if let Some(before) = &before_cursor {
+            if !connection.page_info.has_next_page {
+                connection.page_info.has_next_page =
+                    self.repo.has_users_after(filter, order, before).await?;
+            }
+        }
+
+        Ok(connection)
     }
-    pub async fn find_page_info(
+    /// Decodes the cursors, rejecting cursors created for a different ordering
+    fn convert_cursors(
         &self,
-        first: Option<i32>,
+        order: UserOrder,
         after: Option<String>,
-        last: Option<i32>,
         before: Option<String>,
-    ) -> Result<PageInfo, Error> {
-        let (after_uuid, before_uuid) = convert_params(after, before)?;
+    ) -> Result<(Option<UserCursor>, Option<UserCursor>), Error> {
+        let (after_cursor, before_cursor): (Option<UserCursor>, Option<UserCursor>) =
+            convert_params(after, before)?;
 
-        let users = self
-            .repo
-            .find_all_users(&self.db, first, after_uuid, last, before_uuid)
-            .await?;
+        let mismatch = [&after_cursor, &before_cursor]
+            .into_iter()
+            .flatten()
+            .any(|cursor| cursor.field() != order.field);
+        if mismatch {
+            return Err(Base64CursorError::Invalid.into());
+        }
 
-        let page_info = self
-            .repo
-            .find_page_info(&self.db, &users, first, after_uuid, last, before_uuid)
-            .await?;
-        Ok(page_info)
+        Ok((after_cursor, before_cursor))
     }
 }
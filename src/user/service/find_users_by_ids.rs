@@ -0,0 +1,12 @@
+use uuid::Uuid;
+
+use super::Service;
+use crate::{errors::Error, user::entities::User};
+
+impl Service {
+    pub async fn find_users_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>, Error> {
+        let users = self.repo.find_users_by_ids(ids).await?;
+
+        Ok(users)
+    }
+}
@@ -0,0 +1,10 @@
+use super::Service;
+use crate::{errors::Error, user::entities::User};
+
+impl Service {
+    pub async fn find_users_by_names(&self, names: &[String]) -> Result<Vec<User>, Error> {
+        let users = self.repo.find_users_by_names(names).await?;
+
+        Ok(users)
+    }
+}
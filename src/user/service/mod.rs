@@ -1,36 +1,106 @@
 mod check_username_exists;
+mod count_users;
 mod create_user;
 mod delete_user;
 mod find_user;
+mod find_user_by_name;
+mod find_user_roles;
 mod find_users;
+mod find_users_by_ids;
+mod find_users_by_names;
+mod subscribe;
 mod update_user;
 
+use std::sync::Arc;
+
+use async_graphql::MaybeUndefined;
 use uuid::Uuid;
 
 use crate::{
+    config,
     db::DB,
-    user::{model::input, repository::Repository},
+    events::EventBus,
+    relay::OrderDirection,
+    user::{
+        model::{self, input},
+        repository::{PostgresRepository, UserRepository, UserTransaction},
+        scalar::Time,
+    },
 };
 
 #[derive(Debug)]
 pub struct Service {
-    repo: Repository,
-    pub db: DB,
+    repo: Arc<dyn UserRepository>,
+    events: EventBus,
+    pagination: config::Pagination,
 }
 
 impl Service {
-    pub fn new(db: DB) -> Self {
-        let repo = Repository::new();
-        Self { db, repo }
+    /// Stores the users in Postgres
+    pub fn new(db: DB, events: EventBus, pagination: config::Pagination) -> Self {
+        Self::with_repository(Arc::new(PostgresRepository::new(db)), events, pagination)
+    }
+
+    pub fn with_repository(
+        repo: Arc<dyn UserRepository>,
+        events: EventBus,
+        pagination: config::Pagination,
+    ) -> Self {
+        Self {
+            repo,
+            events,
+            pagination,
+        }
+    }
+
+    /// Starts a unit of work, see `UserTransaction`
+    pub async fn begin(&self) -> Result<Box<dyn UserTransaction>, crate::Error> {
+        Ok(self.repo.begin().await?)
     }
 }
 
-#[derive(Debug)]
-pub struct PageInfo {
-    pub has_next_page: bool,
-    pub has_previous_page: bool,
-    pub start_cursor: Option<String>,
-    pub end_cursor: Option<String>,
+#[derive(Debug, Default, Clone)]
+pub struct UserFilter {
+    /// Case insensitive
+    pub name_contains: Option<String>,
+    pub name_prefix: Option<String>,
+    pub full_name_is_null: Option<bool>,
+    pub created_after: Option<Time>,
+    pub created_before: Option<Time>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UserOrderField {
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    Name,
+}
+
+impl UserOrderField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            UserOrderField::Id => "id",
+            UserOrderField::CreatedAt => "created_at",
+            UserOrderField::UpdatedAt => "updated_at",
+            UserOrderField::Name => "name",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UserOrder {
+    pub field: UserOrderField,
+    pub direction: OrderDirection,
+}
+
+impl Default for UserOrder {
+    fn default() -> Self {
+        Self {
+            field: UserOrderField::Id,
+            direction: OrderDirection::Asc,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -44,16 +114,18 @@ pub struct User {
 pub struct CreateUserInput {
     pub name: String,
     pub full_name: Option<String>,
+    /// Argon2 hash of the User password, if any.
+    pub password_hash: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct UpdateUserInput {
     /// The ID of the User to modify.
     pub id: Uuid,
-    /// The name for the User.
-    pub name: String,
-    /// The full name for the User.
-    pub full_name: Option<String>,
+    /// The name for the User, kept when undefined.
+    pub name: MaybeUndefined<String>,
+    /// The full name for the User, kept when undefined and cleared when null.
+    pub full_name: MaybeUndefined<String>,
 }
 
 impl From<input::CreateUserInput> for CreateUserInput {
@@ -61,16 +133,50 @@ impl From<input::CreateUserInput> for CreateUserInput {
         Self {
             name: user.name,
             full_name: user.full_name,
+            password_hash: None,
         }
     }
 }
 
-impl From<input::UpdateUserInput> for UpdateUserInput {
-    fn from(user: input::UpdateUserInput) -> Self {
-        Self {
-            id: user.id,
+impl TryFrom<input::UpdateUserInput> for UpdateUserInput {
+    type Error = crate::Error;
+
+    fn try_from(user: input::UpdateUserInput) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: model::user_id_of(user.id, user.node_id.as_ref())?,
             name: user.name,
             full_name: user.full_name,
+        })
+    }
+}
+
+impl From<input::UserFilter> for UserFilter {
+    fn from(filter: input::UserFilter) -> Self {
+        Self {
+            name_contains: filter.name_contains,
+            name_prefix: filter.name_prefix,
+            full_name_is_null: filter.full_name_is_null,
+            created_after: filter.created_after,
+            created_before: filter.created_before,
+        }
+    }
+}
+
+impl From<input::UserOrderField> for UserOrderField {
+    fn from(field: input::UserOrderField) -> Self {
+        match field {
+            input::UserOrderField::CreatedAt => UserOrderField::CreatedAt,
+            input::UserOrderField::UpdatedAt => UserOrderField::UpdatedAt,
+            input::UserOrderField::Name => UserOrderField::Name,
+        }
+    }
+}
+
+impl From<input::UserOrder> for UserOrder {
+    fn from(order: input::UserOrder) -> Self {
+        Self {
+            field: order.field.into(),
+            direction: order.direction,
         }
     }
 }
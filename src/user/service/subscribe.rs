@@ -0,0 +1,11 @@
+use tokio::sync::broadcast;
+
+use super::Service;
+use crate::events::Event;
+
+impl Service {
+    /// Subscribe to the user lifecycle events
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
+    }
+}
@@ -1,25 +1,53 @@
+use async_graphql::MaybeUndefined;
 use chrono::Utc;
 
 use super::{Service, UpdateUserInput};
-use crate::{errors, user::entities::User};
+use crate::{
+    errors,
+    events::Event,
+    user::{entities::User, repository::UserTransaction},
+};
 
 impl Service {
+    /// Only changes the defined fields, see `UpdateUserInput`
     pub async fn update_user(&self, input: UpdateUserInput) -> Result<User, errors::Error> {
-        let username_exists = self.check_username_exists(&self.db, &input.name).await?;
-        if username_exists {
-            return Err(errors::core::Error::UsernameAlreadyExists.into());
-        }
+        let mut tx = self.begin().await?;
+        let result = self.update_user_in(tx.as_mut(), input).await;
+        let user = tx.finish(result).await?;
+
+        self.events.publish(Event::UserUpdated(user.clone())).await;
+
+        Ok(user)
+    }
+
+    async fn update_user_in(
+        &self,
+        tx: &mut dyn UserTransaction,
+        input: UpdateUserInput,
+    ) -> Result<User, errors::Error> {
+        let mut user = tx.find_user_by_id_for_update(input.id).await?;
 
-        let user_input = User {
-            id: input.id,
-            name: input.name,
-            full_name: input.full_name,
-            updated_at: Utc::now(),
-            // FIXME
-            created_at: Utc::now(),
-        };
+        match input.name {
+            MaybeUndefined::Undefined => {}
+            MaybeUndefined::Null => return Err(errors::core::Error::UsernameRequired.into()),
+            // Keeping the current name is not a conflict
+            MaybeUndefined::Value(name) if name == user.name => {}
+            MaybeUndefined::Value(name) => {
+                let username_exists = self.check_username_exists(tx, &name).await?;
+                if username_exists {
+                    return Err(errors::core::Error::UsernameAlreadyExists.into());
+                }
+                user.name = name;
+            }
+        }
+        match input.full_name {
+            MaybeUndefined::Undefined => {}
+            MaybeUndefined::Null => user.full_name = None,
+            MaybeUndefined::Value(full_name) => user.full_name = Some(full_name),
+        }
+        user.updated_at = Utc::now();
 
-        let user = self.repo.update_user(&self.db, &user_input).await?;
+        let user = tx.update_user(&user).await?;
 
         Ok(user)
     }
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use async_graphql::{
+    extensions::apollo_persisted_queries::{ApolloPersistedQueries, CacheStorage, LruCacheStorage},
+    value, Request, Schema,
+};
+use graph::{
+    apq::{PersistedQueriesOnly, PostgresStorage},
+    schema::{Mutation, Query, Subscription},
+};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::harness::TestApp;
+
+const QUERY: &str = "{ __typename }";
+
+fn sha256(query: &str) -> String {
+    format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+#[tokio::test]
+async fn register_and_execute_persisted_query() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let hash = sha256(QUERY);
+    let persisted_query = json!({ "persistedQuery": { "version": 1, "sha256Hash": hash } });
+
+    //
+    // unknown hash
+    //
+    let body = test_app
+        .graphql(&json!({ "extensions": persisted_query }), None)
+        .await?;
+    assert_eq!(body["errors"][0]["message"], "PersistedQueryNotFound");
+    //
+    // register
+    //
+    let body = test_app
+        .graphql(
+            &json!({ "query": QUERY, "extensions": persisted_query }),
+            None,
+        )
+        .await?;
+    assert_eq!(body["data"]["__typename"], "Query");
+    //
+    // hash only
+    //
+    let body = test_app
+        .graphql(&json!({ "extensions": persisted_query }), None)
+        .await?;
+    assert_eq!(body["data"]["__typename"], "Query");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn persisted_queries_only() -> Result<()> {
+    let hash = sha256(QUERY);
+    let storage = LruCacheStorage::new(8);
+    storage.set(hash.clone(), QUERY.to_string()).await;
+
+    let schema = Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
+    .extension(PersistedQueriesOnly)
+    .extension(ApolloPersistedQueries::new(storage))
+    .finish();
+
+    let response = schema.execute(Request::new(QUERY)).await;
+    assert_eq!(
+        response.errors[0].message,
+        "only persisted queries are allowed"
+    );
+
+    let mut request = Request::new("");
+    request.extensions.insert(
+        "persistedQuery".to_string(),
+        value!({ "version": 1, "sha256Hash": hash }),
+    );
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty());
+    assert_eq!(response.data, value!({ "__typename": "Query" }));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn postgres_storage_rejects_mismatched_hash() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let storage = PostgresStorage::new(test_app.db.clone());
+    let hash = sha256(QUERY);
+
+    //
+    // another query under the hash
+    //
+    storage
+        .set(hash.clone(), "{ users { totalCount } }".to_string())
+        .await;
+    assert_eq!(storage.get(hash.clone()).await, None);
+    //
+    // its own hash
+    //
+    storage.set(hash.clone(), QUERY.to_string()).await;
+    assert_eq!(storage.get(hash).await, Some(QUERY.to_string()));
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+mod schema {
+    cynic::use_schema!("tests/schema.graphql");
+}
+
+#[cynic::schema_for_derives(file = "tests/schema.graphql", module = "schema")]
+pub mod queries {
+    use super::schema;
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Query")]
+    pub struct MeQuery {
+        pub me: User,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct User {
+        pub id: cynic::Id,
+        pub uuid: Uuid,
+        pub name: String,
+        pub full_name: Option<String>,
+    }
+
+    #[derive(cynic::Scalar, Debug, Clone)]
+    pub struct Uuid(pub String);
+}
+
+#[cynic::schema_for_derives(file = "tests/schema.graphql", module = "schema")]
+pub mod register {
+    use super::schema;
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Mutation", argument_struct = "RegisterInput")]
+    pub struct AuthMutation {
+        #[arguments(input =
+              RegisterInput {
+                 name: args.name.clone(),
+                 full_name: args.full_name.clone(),
+                 password: args.password.clone(),
+            }
+        )]
+        pub register: AuthPayload,
+    }
+
+    #[derive(cynic::InputObject, cynic::FragmentArguments, Debug)]
+    pub struct RegisterInput {
+        pub name: String,
+        pub full_name: Option<String>,
+        pub password: String,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct AuthPayload {
+        pub access_token: String,
+        pub refresh_token: String,
+        pub user: User,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct User {
+        pub id: cynic::Id,
+        pub uuid: Uuid,
+        pub name: String,
+        pub full_name: Option<String>,
+    }
+
+    #[derive(cynic::Scalar, Debug, Clone)]
+    pub struct Uuid(pub String);
+}
+
+#[cynic::schema_for_derives(file = "tests/schema.graphql", module = "schema")]
+pub mod login {
+    use super::schema;
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Mutation", argument_struct = "LoginInput")]
+    pub struct AuthMutation {
+        #[arguments(input =
+              LoginInput {
+                 name: args.name.clone(),
+                 password: args.password.clone(),
+            }
+        )]
+        pub login: AuthPayload,
+    }
+
+    #[derive(cynic::InputObject, cynic::FragmentArguments, Debug)]
+    pub struct LoginInput {
+        pub name: String,
+        pub password: String,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct AuthPayload {
+        pub access_token: String,
+        pub refresh_token: String,
+        pub user: User,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct User {
+        pub id: cynic::Id,
+        pub uuid: Uuid,
+        pub name: String,
+        pub full_name: Option<String>,
+    }
+
+    #[derive(cynic::Scalar, Debug, Clone)]
+    pub struct Uuid(pub String);
+}
+
+#[cynic::schema_for_derives(file = "tests/schema.graphql", module = "schema")]
+pub mod refresh {
+    use super::schema;
+
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Mutation", argument_struct = "RefreshTokenInput")]
+    pub struct AuthMutation {
+        #[arguments(input =
+              RefreshTokenInput {
+                 refresh_token: args.refresh_token.clone(),
+            }
+        )]
+        pub refresh_token: AuthPayload,
+    }
+
+    #[derive(cynic::InputObject, cynic::FragmentArguments, Debug)]
+    pub struct RefreshTokenInput {
+        pub refresh_token: String,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct AuthPayload {
+        pub access_token: String,
+        pub refresh_token: String,
+    }
+}
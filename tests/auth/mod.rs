@@ -0,0 +1,88 @@
+use anyhow::Result;
+use cynic::MutationBuilder;
+use graph::{auth::Principal, user::Role};
+use serde_json::from_value;
+use uuid::Uuid;
+
+use self::{
+    graphql::{login, register},
+    schema::{LoginResponse, RegisterResponse},
+};
+use crate::harness::TestApp;
+
+pub mod graphql;
+pub mod schema;
+mod tests;
+
+pub const PASSWORD: &str = "al-jabr-wal-muqabala";
+
+/// A freshly registered user
+pub struct Registered {
+    pub uuid: Uuid,
+    /// Global id, see `User.id`
+    pub id: String,
+    pub access_token: String,
+}
+
+/// Register a user and returns its ids along with an access token
+pub async fn register(
+    test_app: &TestApp,
+    name: &str,
+    full_name: Option<&str>,
+) -> Result<Registered> {
+    let args = register::RegisterInput {
+        name: name.to_string(),
+        full_name: full_name.map(String::from),
+        password: PASSWORD.to_string(),
+    };
+    let query = register::AuthMutation::build(&args);
+    let body = test_app.graphql(&query, None).await?;
+    let register_response: RegisterResponse = from_value(body)?;
+    let payload = register_response.data.register;
+    let user = payload.user.expect("registered user");
+
+    Ok(Registered {
+        uuid: user.uuid,
+        id: user.id,
+        access_token: payload.access_token,
+    })
+}
+
+/// Login and returns a fresh access token
+pub async fn login(test_app: &TestApp, name: &str) -> Result<String> {
+    let args = login::LoginInput {
+        name: name.to_string(),
+        password: PASSWORD.to_string(),
+    };
+    let query = login::AuthMutation::build(&args);
+    let body = test_app.graphql(&query, None).await?;
+    let login_response: LoginResponse = from_value(body)?;
+
+    Ok(login_response.data.login.access_token)
+}
+
+/// Roles are read when a token is issued, login again to use them
+pub async fn grant_admin(test_app: &TestApp, user_id: Uuid) -> Result<()> {
+    sqlx::query("insert into user_role (user_id, role) values ($1, 'admin')")
+        .bind(user_id)
+        .execute(&test_app.db)
+        .await?;
+
+    Ok(())
+}
+
+/// Register an admin and returns an access token with the role
+pub async fn admin(test_app: &TestApp, name: &str) -> Result<String> {
+    let admin = register(test_app, name, None).await?;
+    grant_admin(test_app, admin.uuid).await?;
+    login(test_app, name).await
+}
+
+/// An admin, for the requests executed on the schema, without a token
+pub fn admin_principal() -> Principal {
+    Principal {
+        user_id: Uuid::nil(),
+        name: String::from("admin"),
+        roles: vec![Role::Admin],
+    }
+}
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+//
+// Register
+//
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterResponse {
+    pub data: RegisterWrapper,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterWrapper {
+    pub register: AuthPayload,
+}
+
+//
+// Login
+//
+
+#[derive(Debug, Deserialize)]
+pub struct LoginResponse {
+    pub data: LoginWrapper,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginWrapper {
+    pub login: AuthPayload,
+}
+
+//
+// Refresh Token
+//
+
+#[derive(Debug, Deserialize)]
+pub struct RefreshTokenResponse {
+    pub data: RefreshTokenWrapper,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct RefreshTokenWrapper {
+    pub refresh_token: AuthPayload,
+}
+
+//
+// Me
+//
+
+#[derive(Debug, Deserialize)]
+pub struct MeResponse {
+    pub data: MeWrapper,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MeWrapper {
+    pub me: User,
+}
+
+//
+// Shared struct
+//
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct AuthPayload {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub user: Option<User>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct User {
+    pub id: String,
+    pub uuid: Uuid,
+    pub name: String,
+    pub full_name: Option<String>,
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use axum::http::StatusCode;
+use cynic::{MutationBuilder, QueryBuilder};
+use serde_json::from_value;
+
+use super::{
+    graphql::{login, queries::MeQuery, refresh, register},
+    schema::{LoginResponse, MeResponse, RefreshTokenResponse, RegisterResponse},
+    PASSWORD,
+};
+use crate::harness::TestApp;
+
+#[tokio::test]
+async fn register_login_and_me() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    //
+    // Register
+    //
+
+    let args = register::RegisterInput {
+        name: "khawa".to_string(),
+        full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
+        password: PASSWORD.to_string(),
+    };
+    let query = register::AuthMutation::build(&args);
+    let body = test_app.graphql(&query, None).await?;
+
+    let register_response: RegisterResponse = from_value(body)?;
+    let registered_user = register_response.data.register.user.expect("user");
+    assert_eq!(registered_user.name, "khawa");
+
+    //
+    // Login
+    //
+
+    let args = login::LoginInput {
+        name: "khawa".to_string(),
+        password: PASSWORD.to_string(),
+    };
+    let query = login::AuthMutation::build(&args);
+    let body = test_app.graphql(&query, None).await?;
+
+    let login_response: LoginResponse = from_value(body)?;
+    let access_token = login_response.data.login.access_token;
+
+    //
+    // Me
+    //
+
+    let query = MeQuery::build(());
+    let body = test_app.graphql(&query, Some(&access_token)).await?;
+
+    let me_response: MeResponse = from_value(body)?;
+    assert_eq!(me_response.data.me.uuid, registered_user.uuid);
+    assert_eq!(me_response.data.me.name, "khawa");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn refresh_token() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let args = register::RegisterInput {
+        name: "khawa".to_string(),
+        full_name: None,
+        password: PASSWORD.to_string(),
+    };
+    let query = register::AuthMutation::build(&args);
+    let body = test_app.graphql(&query, None).await?;
+
+    let register_response: RegisterResponse = from_value(body)?;
+    let refresh_token = register_response.data.register.refresh_token;
+
+    //
+    // A refresh token can't be used as an access token
+    //
+
+    let query = MeQuery::build(());
+    let (status, _) = test_app
+        .graphql_with_status(&query, Some(&refresh_token))
+        .await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    //
+    // Refresh
+    //
+
+    let args = refresh::RefreshTokenInput { refresh_token };
+    let query = refresh::AuthMutation::build(&args);
+    let body = test_app.graphql(&query, None).await?;
+
+    let refresh_response: RefreshTokenResponse = from_value(body)?;
+    assert!(!refresh_response.data.refresh_token.access_token.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn wrong_password() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let args = register::RegisterInput {
+        name: "khawa".to_string(),
+        full_name: None,
+        password: PASSWORD.to_string(),
+    };
+    let query = register::AuthMutation::build(&args);
+    test_app.graphql(&query, None).await?;
+
+    let args = login::LoginInput {
+        name: "khawa".to_string(),
+        password: "wrong-password".to_string(),
+    };
+    let query = login::AuthMutation::build(&args);
+    let body = test_app.graphql(&query, None).await?;
+
+    let error_message = &body["errors"][0]["message"];
+    assert_eq!(error_message, "invalid username or password");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_token() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let query = MeQuery::build(());
+    let (status, body) = test_app
+        .graphql_with_status(&query, Some("invalid_token"))
+        .await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["errors"][0]["extensions"]["code"], "UNAUTHENTICATED");
+
+    Ok(())
+}
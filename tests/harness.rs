@@ -0,0 +1,140 @@
+use std::{sync::Arc, thread};
+
+use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{self, Request, StatusCode},
+    Router,
+};
+use graph::{
+    config::{Config, Secret},
+    db,
+    routes::AppBuilder,
+};
+use serde::Serialize;
+use serde_json::{from_slice, to_string, Value};
+use sqlx::{Connection, PgConnection};
+use tower::util::ServiceExt;
+use ulid::Ulid;
+use url::Url;
+
+/// The app on a database of its own, so tests don't share any state and can run in parallel.
+/// The database is created from `DATABASE_URL`, and dropped along with the `TestApp`.
+pub struct TestApp {
+    pub config: Arc<Config>,
+    /// For the setups the API doesn't cover, e.g: granting roles
+    pub db: db::DB,
+    router: Router,
+    /// Where the test database is created and dropped from
+    admin_url: String,
+    database: String,
+}
+
+impl TestApp {
+    pub async fn new() -> Result<Self> {
+        let mut config = Config::load()?;
+        let admin_url = config.database.url.expose().clone();
+
+        let database = format!("test_{}", Ulid::new().to_string().to_lowercase());
+        let mut conn = PgConnection::connect(&admin_url).await?;
+        sqlx::query(&format!(r#"create database "{}""#, database))
+            .execute(&mut conn)
+            .await?;
+        conn.close().await?;
+
+        let mut url = Url::parse(&admin_url)?;
+        url.set_path(&database);
+        config.database.url = Secret::new(url.to_string());
+        let config = Arc::new(config);
+
+        let db = db::connect(&config.database).await?;
+        db::migrate(&db).await?;
+        let router = AppBuilder::new(Arc::clone(&config), db.clone())
+            .build()
+            .await?;
+
+        Ok(Self {
+            config,
+            db,
+            router,
+            admin_url,
+            database,
+        })
+    }
+
+    /// Requests consume the router, each one needs its own
+    pub fn router(&self) -> Router {
+        self.router.clone()
+    }
+
+    /// Posts a GraphQL request to `/graphql`, as the owner of the access token if any.
+    /// Returns the JSON body of the `200 OK` response.
+    pub async fn graphql(&self, query: &impl Serialize, token: Option<&str>) -> Result<Value> {
+        let (status, body) = self.graphql_with_status(query, token).await?;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        Ok(body)
+    }
+
+    /// Same as `graphql`, for the responses that are not `200 OK`, e.g: an invalid token
+    pub async fn graphql_with_status(
+        &self,
+        query: &impl Serialize,
+        token: Option<&str>,
+    ) -> Result<(StatusCode, Value)> {
+        graphql(self.router(), query, token).await
+    }
+}
+
+/// Posts a GraphQL request to `/graphql` of the given router, e.g: one built with overrides
+pub async fn graphql(
+    router: Router,
+    query: &impl Serialize,
+    token: Option<&str>,
+) -> Result<(StatusCode, Value)> {
+    let mut request = Request::builder()
+        .method(http::Method::POST)
+        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+        .uri("/graphql");
+    if let Some(token) = token {
+        request = request.header(http::header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let request = request.body(Body::from(to_string(query)?))?;
+
+    let response = router.oneshot(request).await?;
+    let status = response.status();
+    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
+    Ok((status, from_slice(&resp_byte)?))
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let admin_url = self.admin_url.clone();
+        let database = self.database.clone();
+
+        // There is no async drop, and the runtime of the test might be shutting down.
+        // `with (force)` closes the connections the app still holds.
+        let dropped = thread::spawn(move || -> Result<()> {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+            runtime.block_on(async {
+                let mut conn = PgConnection::connect(&admin_url).await?;
+                sqlx::query(&format!(
+                    r#"drop database if exists "{}" with (force)"#,
+                    database
+                ))
+                .execute(&mut conn)
+                .await?;
+                conn.close().await?;
+                Ok(())
+            })
+        })
+        .join();
+
+        match dropped {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => eprintln!("dropping test database `{}`: {}", self.database, err),
+            Err(_) => eprintln!("dropping test database `{}`: panicked", self.database),
+        }
+    }
+}
@@ -1,31 +1,22 @@
 use anyhow::Result;
 use axum::{
     body::Body,
-    http::{self, Request, StatusCode},
+    http::{Request, StatusCode},
 };
 use cynic::QueryBuilder;
-use graph::routes::app;
-use serde_json::{from_slice, json, to_string, Value};
+use serde_json::{from_value, json, Value};
 use tower::util::ServiceExt;
 
 use super::{graphql::queries::HealthQuery, schema::HealthResponse};
+use crate::harness::TestApp;
 
 #[tokio::test]
 async fn health() -> Result<()> {
-    let app = app().await?;
+    let test_app = TestApp::new().await?;
 
     let query = HealthQuery::build(());
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.oneshot(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let health_response: HealthResponse = from_slice(&resp_byte)?;
+    let body = test_app.graphql(&query, None).await?;
+    let health_response: HealthResponse = from_value(body)?;
     assert_eq!(health_response.data.health.status, "running");
 
     Ok(())
@@ -33,7 +24,8 @@ async fn health() -> Result<()> {
 
 #[tokio::test]
 async fn health_restapi() -> Result<()> {
-    let app = app().await?;
+    let test_app = TestApp::new().await?;
+    let app = test_app.router();
 
     let request = Request::builder().uri("/health").body(Body::empty())?;
 
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::harness::TestApp;
+
+#[tokio::test]
+async fn query_too_deep() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    // The kind of introspection query a misbehaving client sent us.
+    // Kept under the recursive depth limit, which is checked separately.
+    let type_ref = (0..20).fold(String::from("name"), |inner, _| {
+        format!("ofType {{ {} }}", inner)
+    });
+    let query = format!(
+        "{{ __schema {{ types {{ fields {{ type {{ {} }} }} }} }} }}",
+        type_ref
+    );
+
+    let body = test_app.graphql(&json!({ "query": query }), None).await?;
+    assert_eq!(body["errors"][0]["message"], "Query is nested too deep.");
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_too_complex() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    // Connections cost `first` times their selection
+    let users = "users(first: 100) { edges { node { name fullName } } }";
+    let query = format!("{{ one: {users} two: {users} three: {users} }}");
+
+    let body = test_app.graphql(&json!({ "query": query }), None).await?;
+    assert_eq!(body["errors"][0]["message"], "Query is too complex.");
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_too_complex_without_first() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    // Without `first` nor `last`, connections cost the default page size times their selection
+    let users = "users { edges { node { name fullName } } }";
+    let query = (0..30).fold(String::new(), |query, i| {
+        format!("{} users{}: {}", query, i, users)
+    });
+    let query = format!("{{ {} }}", query);
+
+    let body = test_app.graphql(&json!({ "query": query }), None).await?;
+    assert_eq!(body["errors"][0]["message"], "Query is too complex.");
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_within_limits() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let query = "{ users(first: 10) { edges { node { name } } } }";
+    let body = test_app.graphql(&json!({ "query": query }), None).await?;
+    assert_eq!(body["errors"], Value::Null);
+    Ok(())
+}
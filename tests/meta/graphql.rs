@@ -18,5 +18,13 @@ pub mod queries {
     pub struct Meta {
         pub build: String,
         pub version: String,
+        pub pagination: Pagination,
+    }
+
+    #[derive(cynic::QueryFragment, Debug, Deserialize)]
+    #[serde(rename_all(deserialize = "camelCase"))]
+    pub struct Pagination {
+        pub default_page_size: i32,
+        pub max_page_size: i32,
     }
 }
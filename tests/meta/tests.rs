@@ -1,34 +1,22 @@
 use anyhow::Result;
-use axum::{
-    body::Body,
-    http::{self, Request, StatusCode},
-};
 use cynic::QueryBuilder;
-use graph::routes::app;
-use serde_json::{from_slice, to_string};
-use tower::util::ServiceExt;
+use serde_json::from_value;
 
 use super::{graphql::queries::MetaQuery, schema::MetaResponse};
+use crate::harness::TestApp;
 
 #[tokio::test]
 async fn health() -> Result<()> {
-    let app = app().await?;
+    let test_app = TestApp::new().await?;
 
     let query = MetaQuery::build(());
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.oneshot(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let meta_response: MetaResponse = from_slice(&resp_byte)?;
+    let body = test_app.graphql(&query, None).await?;
+    let meta_response: MetaResponse = from_value(body)?;
 
     let cargo_package_version = env!("CARGO_PKG_VERSION").to_string();
     assert_eq!(meta_response.data.meta.version, cargo_package_version);
+    assert_eq!(meta_response.data.meta.pagination.default_page_size, 10);
+    assert_eq!(meta_response.data.meta.pagination.max_page_size, 100);
 
     Ok(())
 }
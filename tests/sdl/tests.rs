@@ -0,0 +1,19 @@
+use anyhow::Result;
+use graph::schema::{self, diff::diff};
+
+#[test]
+fn schema_up_to_date() -> Result<()> {
+    let changes = diff(
+        include_str!("../schema.graphql"),
+        &schema::build().finish().sdl(),
+    )?;
+
+    let changes: Vec<String> = changes.iter().map(ToString::to_string).collect();
+    assert!(
+        changes.is_empty(),
+        "`tests/schema.graphql` is outdated, run `make update_graphql_schema`:\n{}",
+        changes.join("\n")
+    );
+
+    Ok(())
+}
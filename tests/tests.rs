@@ -1,3 +1,8 @@
+mod apq;
+mod auth;
+mod harness;
 mod health;
+mod limits;
 mod meta;
+mod sdl;
 mod user;
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::http::StatusCode;
+use graph::{events::EventBus, routes::AppBuilder, user};
+use serde_json::json;
+
+use crate::harness::{graphql, TestApp};
+
+#[tokio::test]
+async fn override_user_service() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let user_service = Arc::new(user::Service::with_repository(
+        Arc::new(user::MemoryRepository::new()),
+        EventBus::new(),
+        test_app.config.pagination.clone(),
+    ));
+    let app = AppBuilder::new(Arc::clone(&test_app.config), test_app.db.clone())
+        .user_service(user_service)
+        .build()
+        .await?;
+
+    let query = json!({
+        "query": r#"mutation { register(input: { name: "khawa", password: "passw0rd!" }) { user { name } } }"#,
+    });
+    let (status, body) = graphql(app, &query, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["register"]["user"]["name"], "khawa");
+
+    // The user went to the repository of the override, not to the database
+    let (count,): (i64,) = sqlx::query_as("select count(*) from user_")
+        .fetch_one(&test_app.db)
+        .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
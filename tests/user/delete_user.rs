@@ -1,86 +1,43 @@
 use anyhow::Result;
-use axum::{
-    body::Body,
-    http::{self, Request, StatusCode},
-};
 use cynic::{MutationBuilder, QueryBuilder};
-use graph::routes::app;
-use serde_json::{from_slice, to_string, Value};
-use tower::{util::ServiceExt, Service};
 
-use super::{
-    graphql::{
-        add, delete, queries,
-        queries::{ReadUserArguments, UserQuery},
-    },
-    schema::CreateUserResponse,
+use super::graphql::{
+    delete, queries,
+    queries::{ReadUserArguments, UserQuery},
 };
-use crate::user::teardown;
+use crate::{auth::register, harness::TestApp};
 
 #[tokio::test]
 async fn delete_user() -> Result<()> {
-    let mut app = app().await?;
+    let test_app = TestApp::new().await?;
 
     //
     // Create User
     //
 
-    let args = add::CreateUserInput {
-        name: "khawa".to_string(),
-        full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
-    };
-    let query = add::UserMutation::build(&args);
-
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let user_response: CreateUserResponse = from_slice(&resp_byte)?;
-    assert_eq!(user_response.data.create_user.name, "khawa");
-
-    let user_id = user_response.data.create_user.id;
+    let user = register(&test_app, "khawa", Some("Abu Musa Al-Khawarizmi")).await?;
 
     //
-    // Update User
+    // Delete User
     //
 
-    let user_id_str = delete::Uuid(user_id.to_string());
-    let args = delete::DeleteUserArguments { id: user_id_str };
+    let args = delete::DeleteUserArguments {
+        id: Some(delete::Uuid(user.uuid.to_string())),
+    };
     let query = delete::UserMutation::build(&args);
+    test_app.graphql(&query, Some(&user.access_token)).await?;
 
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let _ = app.ready().await?.call(request).await?;
     //
     // Make sure user deleted
     //
     let args = ReadUserArguments {
-        id: queries::Uuid(user_id.to_string()),
+        id: Some(queries::Uuid(user.uuid.to_string())),
     };
     let query = UserQuery::build(args);
+    let body = test_app.graphql(&query, None).await?;
 
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let body: Value = from_slice(&resp_byte)?;
     let error_message = &body["errors"][0]["message"];
     assert_eq!(error_message, "user not found");
 
-    teardown().await?;
     Ok(())
 }
@@ -1,19 +1,20 @@
+use std::sync::Arc;
+
 use anyhow::Result;
-use axum::{
-    body::Body,
-    http::{self, Request, StatusCode},
-};
 use cynic::MutationBuilder;
-use graph::routes::app;
-use serde_json::{from_slice, to_string, Value};
-use tower::{util::ServiceExt, Service};
+use serde_json::to_value;
 
-use super::{graphql::add, schema::CreateUserResponse};
-use crate::user::{graphql::update, teardown};
+use super::graphql::add;
+use crate::{
+    auth::{admin, register},
+    harness::TestApp,
+    user::graphql::update,
+};
 
 #[tokio::test]
 async fn duplicate_username_create() -> Result<()> {
-    let mut app = app().await?;
+    let test_app = TestApp::new().await?;
+    let access_token = admin(&test_app, "haitham").await?;
     //
     // Create User
     //
@@ -23,14 +24,7 @@ async fn duplicate_username_create() -> Result<()> {
         full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
     };
     let query = add::UserMutation::build(&args);
-
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let _ = app.ready().await?.call(request).await?;
+    test_app.graphql(&query, Some(&access_token)).await?;
 
     //
     // Create next user with the same name
@@ -41,93 +35,84 @@ async fn duplicate_username_create() -> Result<()> {
         full_name: None,
     };
     let query = add::UserMutation::build(&args);
+    let body = test_app.graphql(&query, Some(&access_token)).await?;
 
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let body: Value = from_slice(&resp_byte)?;
     let error_message = &body["errors"][0]["message"];
     assert_eq!(error_message, "username is already in use");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "ALREADY_EXISTS");
 
-    teardown().await?;
     Ok(())
 }
 
 #[tokio::test]
 async fn duplicate_username_update() -> Result<()> {
-    let mut router = app().await?;
-    let app = router.ready().await?;
+    let test_app = TestApp::new().await?;
     //
     // Create User
     //
 
-    let args = add::CreateUserInput {
-        name: "khawa".to_string(),
-        full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
-    };
-    let query = add::UserMutation::build(&args);
-
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
+    register(&test_app, "khawa", Some("Abu Musa Al-Khawarizmi")).await?;
 
     //
     // Create second user
     //
 
-    let args = add::CreateUserInput {
-        name: "khawa1".to_string(),
-        full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
-    };
-    let query = add::UserMutation::build(&args);
-
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let user_response: CreateUserResponse = from_slice(&resp_byte)?;
-    let user_id = user_response.data.create_user.id;
+    let user = register(&test_app, "khawa1", Some("Abu Musa Al-Khawarizmi")).await?;
 
     //
     // Update second user to the same name as first user
     //
 
-    let user_id = update::Uuid(user_id.to_string());
     let args = update::UpdateUserInput {
-        id: user_id,
-        name: "khawa".to_string(),
+        id: Some(update::Uuid(user.uuid.to_string())),
+        name: Some("khawa".to_string()),
         full_name: None,
     };
     let query = update::UserMutation::build(&args);
+    let body = test_app.graphql(&query, Some(&user.access_token)).await?;
 
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let body: Value = from_slice(&resp_byte)?;
     let error_message = &body["errors"][0]["message"];
     assert_eq!(error_message, "username is already in use");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "ALREADY_EXISTS");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn duplicate_username_concurrent_create() -> Result<()> {
+    let test_app = Arc::new(TestApp::new().await?);
+    let access_token = admin(&test_app, "haitham").await?;
+
+    let args = add::CreateUserInput {
+        name: "khawa".to_string(),
+        full_name: None,
+    };
+    let query = to_value(&add::UserMutation::build(&args))?;
+
+    //
+    // Create the same user from concurrent requests
+    //
+
+    let mut handles = Vec::new();
+    for _ in 0..8 {
+        let test_app = Arc::clone(&test_app);
+        let query = query.clone();
+        let access_token = access_token.clone();
+        handles.push(tokio::spawn(async move {
+            test_app.graphql(&query, Some(&access_token)).await
+        }));
+    }
+
+    let mut created = 0;
+    for handle in handles {
+        let body = handle.await??;
+        if body["errors"].is_null() {
+            created += 1;
+        } else {
+            assert_eq!(body["errors"][0]["extensions"]["code"], "ALREADY_EXISTS");
+        }
+    }
+    assert_eq!(created, 1);
 
-    teardown().await?;
     Ok(())
 }
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use cynic::QueryBuilder;
+use serde_json::from_value;
+
+use super::{
+    graphql::queries::{
+        FilterUsersArguments, FilteredUsersQuery, OrderDirection, UserFilter, UserOrder,
+        UserOrderField,
+    },
+    relay::create_users,
+    schema::UsersResponse,
+};
+use crate::harness::TestApp;
+
+#[tokio::test]
+async fn filter_users_by_name() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    create_users(&test_app).await?;
+
+    let args = FilterUsersArguments {
+        first: Some(10),
+        after: None,
+        filter: Some(UserFilter {
+            name_contains: Some("O".to_string()),
+            name_prefix: None,
+        }),
+        order_by: Some(UserOrder {
+            field: UserOrderField::Name,
+            direction: OrderDirection::Asc,
+        }),
+    };
+    let query = FilteredUsersQuery::build(args);
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
+    assert_eq!(users_response.data.users.total_count, 3);
+
+    let names: Vec<&str> = users_response
+        .data
+        .users
+        .edges
+        .iter()
+        .map(|edge| edge.node.name.as_str())
+        .collect();
+    assert_eq!(names, ["four", "one", "two"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn order_users_by_name_desc() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    create_users(&test_app).await?;
+
+    let order_by = UserOrder {
+        field: UserOrderField::Name,
+        direction: OrderDirection::Desc,
+    };
+    let args = FilterUsersArguments {
+        first: Some(2),
+        after: None,
+        filter: None,
+        order_by: Some(order_by.clone()),
+    };
+    let query = FilteredUsersQuery::build(args);
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
+    assert_eq!(users_response.data.users.total_count, 6);
+    assert_eq!(users_response.data.users.edges[0].node.name, "two");
+    assert_eq!(users_response.data.users.edges[1].node.name, "three");
+
+    let three_cursor = users_response.data.users.edges[1].cursor.clone();
+    //
+    // after
+    //
+    let args = FilterUsersArguments {
+        first: Some(2),
+        after: Some(three_cursor),
+        filter: None,
+        order_by: Some(order_by),
+    };
+    let query = FilteredUsersQuery::build(args);
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
+    assert_eq!(users_response.data.users.edges[0].node.name, "six");
+    assert_eq!(users_response.data.users.edges[1].node.name, "one");
+
+    Ok(())
+}
@@ -22,10 +22,60 @@ pub mod queries {
         pub before: Option<String>,
     }
 
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(graphql_type = "Query", argument_struct = "FilterUsersArguments")]
+    pub struct FilteredUsersQuery {
+        #[arguments(first = &args.first, after = &args.after, filter = &args.filter, order_by = &args.order_by)]
+        pub users: UserConnection,
+    }
+
+    // All sturct must be inline
+    #[derive(cynic::FragmentArguments, Debug)]
+    pub struct FilterUsersArguments {
+        pub first: Option<i32>,
+        pub after: Option<String>,
+        pub filter: Option<UserFilter>,
+        pub order_by: Option<UserOrder>,
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone, Default)]
+    pub struct UserFilter {
+        pub name_contains: Option<String>,
+        pub name_prefix: Option<String>,
+    }
+
+    #[derive(cynic::InputObject, Debug, Clone)]
+    pub struct UserOrder {
+        pub field: UserOrderField,
+        pub direction: OrderDirection,
+    }
+
+    #[derive(cynic::Enum, Debug, Clone, Copy)]
+    pub enum UserOrderField {
+        CreatedAt,
+        Name,
+        UpdatedAt,
+    }
+
+    #[derive(cynic::Enum, Debug, Clone, Copy)]
+    pub enum OrderDirection {
+        Asc,
+        Desc,
+    }
+
     #[derive(cynic::QueryFragment, Debug)]
     pub struct UserConnection {
         pub total_count: i32,
         pub edges: Vec<UserEdge>,
+        pub page_info: PageInfo,
+    }
+
+    #[derive(cynic::QueryFragment, Debug)]
+    pub struct PageInfo {
+        pub has_next_page: bool,
+        pub has_previous_page: bool,
+        pub start_cursor: Option<String>,
+        pub end_cursor: Option<String>,
     }
 
     #[derive(cynic::QueryFragment, Debug)]
@@ -36,7 +86,8 @@ pub mod queries {
 
     #[derive(cynic::QueryFragment, Debug)]
     pub struct User {
-        pub id: Uuid,
+        pub id: cynic::Id,
+        pub uuid: Uuid,
         pub name: String,
         pub full_name: Option<String>,
     }
@@ -51,7 +102,7 @@ pub mod queries {
     // All sturct must be inline
     #[derive(cynic::FragmentArguments, Debug)]
     pub struct ReadUserArguments {
-        pub id: Uuid,
+        pub id: Option<Uuid>,
     }
 
     // All sturct must be inline
@@ -83,7 +134,8 @@ pub mod add {
 
     #[derive(cynic::QueryFragment, Debug)]
     pub struct User {
-        pub id: Uuid,
+        pub id: cynic::Id,
+        pub uuid: Uuid,
         pub name: String,
         pub full_name: Option<String>,
     }
@@ -111,14 +163,15 @@ pub mod update {
 
     #[derive(cynic::InputObject, cynic::FragmentArguments, Debug)]
     pub struct UpdateUserInput {
-        pub id: Uuid,
-        pub name: String,
+        pub id: Option<Uuid>,
+        pub name: Option<String>,
         pub full_name: Option<String>,
     }
 
     #[derive(cynic::QueryFragment, Debug)]
     pub struct User {
-        pub id: Uuid,
+        pub id: cynic::Id,
+        pub uuid: Uuid,
         pub name: String,
         pub full_name: Option<String>,
     }
@@ -139,12 +192,13 @@ pub mod delete {
 
     #[derive(cynic::FragmentArguments, Debug)]
     pub struct DeleteUserArguments {
-        pub id: Uuid,
+        pub id: Option<Uuid>,
     }
 
     #[derive(cynic::QueryFragment, Debug)]
     pub struct User {
-        pub id: Uuid,
+        pub id: cynic::Id,
+        pub uuid: Uuid,
         pub name: String,
         pub full_name: Option<String>,
     }
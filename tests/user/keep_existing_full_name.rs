@@ -1,70 +1,83 @@
 use anyhow::Result;
-use axum::{
-    body::Body,
-    http::{self, Request},
-};
-use cynic::MutationBuilder;
-use graph::routes::app;
-use serde_json::{from_slice, to_string};
-use tower::{util::ServiceExt, Service};
-
-use super::{
-    graphql::{add, update},
-    schema::{CreateUserResponse, UpdateUserResponse},
-};
-use crate::user::teardown;
+use serde_json::{from_value, json};
+
+use super::schema::UpdateUserResponse;
+use crate::{auth::register, harness::TestApp};
+
+// Cynic can't tell an absent field from a null one, so these use raw variables.
+const UPDATE_USER: &str = "mutation UpdateUser($input: UpdateUserInput!) {
+    updateUser(input: $input) { id uuid name fullName }
+}";
 
 #[tokio::test]
 async fn keep_existing_full_name() -> Result<()> {
-    let mut app = app().await?;
+    let test_app = TestApp::new().await?;
     //
     // Create User
     //
 
-    let args = add::CreateUserInput {
-        name: "khawa".to_string(),
-        full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
-    };
-    let query = add::UserMutation::build(&args);
-
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let user_response: CreateUserResponse = from_slice(&resp_byte)?;
-    let user_id = user_response.data.create_user.id;
+    let user = register(&test_app, "khawa", Some("Abu Musa Al-Khawarizmi")).await?;
     //
     // Update Only the user name
     //
-    let args = update::UpdateUserInput {
-        id: update::Uuid(user_id.to_string()),
-        name: "khawa1".to_string(),
-        full_name: None,
-    };
-    let query = update::UserMutation::build(&args);
-
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.ready().await?.call(request).await?;
+    let query = json!({ "query": UPDATE_USER, "variables": { "input": { "id": user.uuid, "name": "khawa1" } } });
+    let body = test_app.graphql(&query, Some(&user.access_token)).await?;
     //
     // Make sure the full name preserved
     //
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let user_response: UpdateUserResponse = from_slice(&resp_byte)?;
+    let user_response: UpdateUserResponse = from_value(body)?;
     assert_eq!(user_response.data.update_user.name, "khawa1");
     assert_eq!(
         user_response.data.update_user.full_name,
         Some("Abu Musa Al-Khawarizmi".to_string())
     );
 
-    teardown().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn keep_existing_name() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let user = register(&test_app, "khawa", Some("Abu Musa Al-Khawarizmi")).await?;
+
+    let query = json!({ "query": UPDATE_USER, "variables": { "input": { "id": user.uuid, "fullName": "Al-Khawarizmi" } } });
+    let body = test_app.graphql(&query, Some(&user.access_token)).await?;
+
+    let user_response: UpdateUserResponse = from_value(body)?;
+    assert_eq!(user_response.data.update_user.name, "khawa");
+    assert_eq!(
+        user_response.data.update_user.full_name,
+        Some("Al-Khawarizmi".to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn clear_full_name() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let user = register(&test_app, "khawa", Some("Abu Musa Al-Khawarizmi")).await?;
+
+    let query = json!({ "query": UPDATE_USER, "variables": { "input": { "id": user.uuid, "fullName": null } } });
+    let body = test_app.graphql(&query, Some(&user.access_token)).await?;
+
+    let user_response: UpdateUserResponse = from_value(body)?;
+    assert_eq!(user_response.data.update_user.name, "khawa");
+    assert_eq!(user_response.data.update_user.full_name, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn null_name() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let user = register(&test_app, "khawa", None).await?;
+
+    let query = json!({ "query": UPDATE_USER, "variables": { "input": { "id": user.uuid, "name": null } } });
+    let body = test_app.graphql(&query, Some(&user.access_token)).await?;
+
+    assert_eq!(body["errors"][0]["message"], "username can't be null");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_ARGUMENT");
+
     Ok(())
 }
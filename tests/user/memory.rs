@@ -0,0 +1,185 @@
+// Runs against `MemoryRepository`, in-process.
+// These don't need a database, nor a `TestApp`.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_graphql::{dataloader::DataLoader, Request, Schema};
+use graph::{
+    auth,
+    config::Config,
+    context::ServerContext,
+    events::EventBus,
+    health, meta,
+    schema::{AppSchema, Mutation, Query, Subscription},
+    user,
+};
+use serde_json::Value;
+
+use crate::auth::admin_principal;
+
+fn schema(repo: user::MemoryRepository) -> Result<(AppSchema, Arc<auth::Service>)> {
+    let config = Config::load()?;
+
+    let user_service = Arc::new(user::Service::with_repository(
+        Arc::new(repo),
+        EventBus::new(),
+        config.pagination.clone(),
+    ));
+    let auth_service = Arc::new(auth::Service::new(&config.jwt, Arc::clone(&user_service)));
+    let user_loader = DataLoader::new(
+        user::UserLoader::new(Arc::clone(&user_service)),
+        tokio::spawn,
+    );
+    let server_context = Arc::new(ServerContext {
+        user_service,
+        auth_service: Arc::clone(&auth_service),
+        meta_service: Arc::new(meta::Service::new(config.pagination.clone())),
+        health_service: Arc::new(health::Service::new()),
+    });
+
+    let schema = Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
+    .data(server_context)
+    .data(user_loader)
+    .finish();
+    Ok((schema, auth_service))
+}
+
+async fn execute(schema: &AppSchema, request: impl Into<Request>) -> Result<Value> {
+    let response = schema.execute(request).await;
+    Ok(serde_json::to_value(&response)?)
+}
+
+#[tokio::test]
+async fn create_and_list_users() -> Result<()> {
+    let (schema, _) = schema(user::MemoryRepository::new())?;
+
+    for name in ["khawa", "haitham", "ibnu"] {
+        let query = format!(
+            r#"mutation {{ createUser(input: {{ name: "{}" }}) {{ name }} }}"#,
+            name
+        );
+        let body = execute(&schema, Request::new(query).data(admin_principal())).await?;
+        assert_eq!(body["data"]["createUser"]["name"], name);
+    }
+
+    let query = r#"{
+        users(first: 2, orderBy: { field: NAME }) {
+            totalCount
+            edges { node { name } }
+            pageInfo { hasNextPage endCursor }
+        }
+    }"#;
+    let body = execute(&schema, query).await?;
+    let users = &body["data"]["users"];
+    assert_eq!(users["totalCount"], 3);
+    assert_eq!(users["edges"][0]["node"]["name"], "haitham");
+    assert_eq!(users["edges"][1]["node"]["name"], "ibnu");
+    assert_eq!(users["pageInfo"]["hasNextPage"], true);
+
+    let query = format!(
+        r#"{{ users(first: 2, after: {}, orderBy: {{ field: NAME }}) {{
+            edges {{ node {{ name }} }}
+            pageInfo {{ hasNextPage hasPreviousPage }}
+        }} }}"#,
+        users["pageInfo"]["endCursor"]
+    );
+    let body = execute(&schema, query).await?;
+    let users = &body["data"]["users"];
+    assert_eq!(users["edges"][0]["node"]["name"], "khawa");
+    assert_eq!(users["pageInfo"]["hasNextPage"], false);
+    assert_eq!(users["pageInfo"]["hasPreviousPage"], true);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn duplicate_username() -> Result<()> {
+    let (schema, _) = schema(user::MemoryRepository::new())?;
+
+    let query = r#"mutation { createUser(input: { name: "khawa" }) { name } }"#;
+    let body = execute(&schema, Request::new(query).data(admin_principal())).await?;
+    assert_eq!(body["data"]["createUser"]["name"], "khawa");
+
+    let body = execute(&schema, Request::new(query).data(admin_principal())).await?;
+    assert_eq!(body["errors"][0]["message"], "username is already in use");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "ALREADY_EXISTS");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_user() -> Result<()> {
+    let (schema, auth_service) = schema(user::MemoryRepository::new())?;
+
+    let body = execute(
+        &schema,
+        r#"mutation {
+            register(input: { name: "khawa", fullName: "Abu Musa Al-Khawarizmi", password: "passw0rd!" }) {
+                accessToken
+                user { uuid }
+            }
+        }"#,
+    )
+    .await?;
+    let access_token = body["data"]["register"]["accessToken"]
+        .as_str()
+        .expect("access token");
+    let user_id = &body["data"]["register"]["user"]["uuid"];
+    let principal = auth_service.authenticate(access_token)?;
+
+    let query = format!(
+        r#"mutation {{ updateUser(input: {{ id: {}, name: "khawa1", fullName: null }}) {{ name fullName }} }}"#,
+        user_id
+    );
+    let body = execute(&schema, Request::new(query).data(principal)).await?;
+    assert_eq!(body["data"]["updateUser"]["name"], "khawa1");
+    assert_eq!(body["data"]["updateUser"]["fullName"], Value::Null);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn admin_update_other_user() -> Result<()> {
+    let repo = user::MemoryRepository::new();
+    let (schema, auth_service) = schema(repo.clone())?;
+
+    let mut ids = Vec::new();
+    for name in ["khawa", "haitham"] {
+        let body = execute(
+            &schema,
+            format!(
+                r#"mutation {{ register(input: {{ name: "{}", password: "passw0rd!" }}) {{ user {{ uuid }} }} }}"#,
+                name
+            ),
+        )
+        .await?;
+        ids.push(body["data"]["register"]["user"]["uuid"].clone());
+    }
+    let admin_id = ids[1].as_str().expect("user id").parse()?;
+    repo.grant_admin(admin_id).await;
+
+    // Roles are read on login
+    let body = execute(
+        &schema,
+        r#"mutation { login(input: { name: "haitham", password: "passw0rd!" }) { accessToken } }"#,
+    )
+    .await?;
+    let access_token = body["data"]["login"]["accessToken"]
+        .as_str()
+        .expect("access token");
+    let principal = auth_service.authenticate(access_token)?;
+
+    let query = format!(
+        r#"mutation {{ updateUser(input: {{ id: {}, name: "khawa1" }}) {{ name }} }}"#,
+        ids[0]
+    );
+    let body = execute(&schema, Request::new(query).data(principal)).await?;
+    assert_eq!(body["data"]["updateUser"]["name"], "khawa1");
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{
+    auth::{admin, register},
+    harness::TestApp,
+};
+
+#[tokio::test]
+async fn find_user_by_node_id() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let access_token = admin(&test_app, "haitham").await?;
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": r#"mutation { createUser(input: { name: "khawa" }) { id uuid } }"#,
+            }),
+            Some(&access_token),
+        )
+        .await?;
+    let user = &body["data"]["createUser"];
+    let id = user["id"].as_str().expect("node id");
+    assert_ne!(id, user["uuid"]);
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "query($id: ID!) { node(id: $id) { __typename id ... on User { name } } }",
+                "variables": { "id": id },
+            }),
+            None,
+        )
+        .await?;
+    let node = &body["data"]["node"];
+    assert_eq!(node["__typename"], "User");
+    assert_eq!(node["id"], id);
+    assert_eq!(node["name"], "khawa");
+
+    //
+    // nodes keep the order, and are null for unknown ids
+    //
+    let unknown_id = base64::encode_config(
+        "User:017eb8d1-a5b5-9443-2d94-b6ad7787bf0e",
+        base64::URL_SAFE_NO_PAD,
+    );
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "query($ids: [ID!]!) { nodes(ids: $ids) { id } }",
+                "variables": { "ids": [unknown_id, id] },
+            }),
+            None,
+        )
+        .await?;
+    let nodes = &body["data"]["nodes"];
+    assert_eq!(nodes[0], Value::Null);
+    assert_eq!(nodes[1]["id"], id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_node_id() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": r#"{ node(id: "invalid") { id } }"#,
+            }),
+            None,
+        )
+        .await?;
+    assert_eq!(body["errors"][0]["message"], "invalid node id");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_ARGUMENT");
+    Ok(())
+}
+
+#[tokio::test]
+async fn find_user_by_id() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let access_token = admin(&test_app, "haitham").await?;
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": r#"mutation { createUser(input: { name: "khawa" }) { id uuid } }"#,
+            }),
+            Some(&access_token),
+        )
+        .await?;
+    let user = &body["data"]["createUser"];
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "query($id: ID!) { user(nodeId: $id) { id name } }",
+                "variables": { "id": user["id"] },
+            }),
+            None,
+        )
+        .await?;
+    assert_eq!(body["data"]["user"]["id"], user["id"]);
+    assert_eq!(body["data"]["user"]["name"], "khawa");
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "query($uuid: UUID!) { user(id: $uuid) { id name } }",
+                "variables": { "uuid": user["uuid"] },
+            }),
+            None,
+        )
+        .await?;
+    assert_eq!(body["data"]["user"]["id"], user["id"]);
+    assert_eq!(body["data"]["user"]["name"], "khawa");
+
+    //
+    // The uuid is not a global id
+    //
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "query($id: ID!) { user(nodeId: $id) { id } }",
+                "variables": { "id": user["uuid"] },
+            }),
+            None,
+        )
+        .await?;
+    assert_eq!(body["errors"][0]["message"], "invalid node id");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_ARGUMENT");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn user_id_of_another_type() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let id = base64::encode_config(
+        "Post:017eb8d1-a5b5-9443-2d94-b6ad7787bf0e",
+        base64::URL_SAFE_NO_PAD,
+    );
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "query($id: ID!) { user(nodeId: $id) { id } }",
+                "variables": { "id": id },
+            }),
+            None,
+        )
+        .await?;
+    assert_eq!(body["errors"][0]["message"], "invalid node id");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_ARGUMENT");
+    Ok(())
+}
+
+#[tokio::test]
+async fn exactly_one_user_id() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let user = register(&test_app, "khawa", None).await?;
+
+    //
+    // neither
+    //
+    let body = test_app
+        .graphql(&json!({ "query": "{ user { id } }" }), None)
+        .await?;
+    assert_eq!(
+        body["errors"][0]["message"],
+        "exactly one of `id` and `nodeId` must be given"
+    );
+    assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_ARGUMENT");
+    //
+    // both
+    //
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "query($uuid: UUID!, $id: ID!) { user(id: $uuid, nodeId: $id) { id } }",
+                "variables": { "uuid": user.uuid.to_string(), "id": user.id },
+            }),
+            None,
+        )
+        .await?;
+    assert_eq!(
+        body["errors"][0]["message"],
+        "exactly one of `id` and `nodeId` must be given"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_and_delete_user_by_node_id() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let user = register(&test_app, "khawa", None).await?;
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": r#"mutation($id: ID!) { updateUser(input: { nodeId: $id, name: "khawa1" }) { name } }"#,
+                "variables": { "id": user.id },
+            }),
+            Some(&user.access_token),
+        )
+        .await?;
+    assert_eq!(body["data"]["updateUser"]["name"], "khawa1");
+
+    let body = test_app
+        .graphql(
+            &json!({
+                "query": "mutation($id: ID!) { deleteUser(nodeId: $id) { name } }",
+                "variables": { "id": user.id },
+            }),
+            Some(&user.access_token),
+        )
+        .await?;
+    assert_eq!(body["data"]["deleteUser"]["name"], "khawa1");
+
+    Ok(())
+}
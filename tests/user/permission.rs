@@ -0,0 +1,70 @@
+use anyhow::Result;
+use cynic::MutationBuilder;
+use serde_json::from_value;
+
+use super::{
+    graphql::{delete, update},
+    schema::UpdateUserResponse,
+};
+use crate::{
+    auth::{admin, register},
+    harness::TestApp,
+};
+
+#[tokio::test]
+async fn update_other_user() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let user = register(&test_app, "khawa", None).await?;
+    let access_token = register(&test_app, "haitham", None).await?.access_token;
+
+    let args = update::UpdateUserInput {
+        id: Some(update::Uuid(user.uuid.to_string())),
+        name: Some("khawa1".to_string()),
+        full_name: None,
+    };
+    let query = update::UserMutation::build(&args);
+
+    let body = test_app.graphql(&query, Some(&access_token)).await?;
+    assert_eq!(body["errors"][0]["extensions"]["code"], "PERMISSION_DENIED");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn admin_update_other_user() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let user = register(&test_app, "khawa", None).await?;
+    let access_token = admin(&test_app, "haitham").await?;
+
+    let args = update::UpdateUserInput {
+        id: Some(update::Uuid(user.uuid.to_string())),
+        name: Some("khawa1".to_string()),
+        full_name: None,
+    };
+    let query = update::UserMutation::build(&args);
+
+    let body = test_app.graphql(&query, Some(&access_token)).await?;
+    let user_response: UpdateUserResponse = from_value(body)?;
+    assert_eq!(user_response.data.update_user.name, "khawa1");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_user_unauthenticated() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let user = register(&test_app, "khawa", None).await?;
+
+    let args = delete::DeleteUserArguments {
+        id: Some(delete::Uuid(user.uuid.to_string())),
+    };
+    let query = delete::UserMutation::build(&args);
+
+    let body = test_app.graphql(&query, None).await?;
+    assert_eq!(body["errors"][0]["extensions"]["code"], "UNAUTHENTICATED");
+
+    Ok(())
+}
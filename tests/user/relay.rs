@@ -1,27 +1,31 @@
 use anyhow::Result;
-use axum::{
-    body::Body,
-    http::{self, Request, StatusCode},
-};
 use cynic::{MutationBuilder, QueryBuilder};
-use graph::routes::app;
-use serde_json::{from_slice, to_string, Value};
-use tower::{util::ServiceExt, Service};
+use serde_json::from_value;
 
 use super::{
     graphql::{
         add,
         queries::{ReadUsersArguments, UsersQuery},
     },
-    schema::UsersResponse,
+    schema::{UserConnection, UsersResponse},
 };
-use crate::user::teardown;
+use crate::{auth::admin, harness::TestApp};
 
 #[tokio::test]
 async fn no_first_no_last() -> Result<()> {
-    let mut router = app().await?;
-    let app = router.ready().await?;
-    create_users().await?;
+    let test_app = TestApp::new().await?;
+
+    // One more than a page, the first user creates the others
+    let default_page_size = test_app.config.pagination.default_page_size;
+    let access_token = admin(&test_app, "user000").await?;
+    for i in 1..=default_page_size {
+        let args = add::CreateUserInput {
+            name: format!("user{:03}", i),
+            full_name: None,
+        };
+        let query = add::UserMutation::build(&args);
+        test_app.graphql(&query, Some(&access_token)).await?;
+    }
 
     let args = ReadUsersArguments {
         first: None,
@@ -30,29 +34,19 @@ async fn no_first_no_last() -> Result<()> {
         before: None,
     };
     let query = UsersQuery::build(args);
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let body: Value = from_slice(&resp_byte)?;
-    let error_message = &body["errors"][0]["message"];
-    assert_eq!(
-        error_message,
-        "You must provide a `first` or `last` value to properly paginate the entity."
-    );
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
+    let users = users_response.data.users;
+    assert_eq!(users.edges.len(), usize::try_from(default_page_size)?);
+    assert!(users.page_info.has_next_page);
+    assert!(!users.page_info.has_previous_page);
     Ok(())
 }
 
 #[tokio::test]
 async fn both_first_and_last() -> Result<()> {
-    let mut router = app().await?;
-    let app = router.ready().await?;
-    create_users().await?;
+    let test_app = TestApp::new().await?;
+    create_users(&test_app).await?;
 
     let args = ReadUsersArguments {
         first: Some(1),
@@ -61,16 +55,7 @@ async fn both_first_and_last() -> Result<()> {
         before: None,
     };
     let query = UsersQuery::build(args);
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let body: Value = from_slice(&resp_byte)?;
+    let body = test_app.graphql(&query, None).await?;
     let error_message = &body["errors"][0]["message"];
     assert_eq!(
         error_message,
@@ -79,11 +64,32 @@ async fn both_first_and_last() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn page_size_out_of_range() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    for (first, last) in [(Some(1_000_000), None), (None, Some(-1))] {
+        let args = ReadUsersArguments {
+            first,
+            after: None,
+            last,
+            before: None,
+        };
+        let query = UsersQuery::build(args);
+        let body = test_app.graphql(&query, None).await?;
+        let error_message = &body["errors"][0]["message"];
+        assert_eq!(
+            error_message,
+            "`first` and `last` must be between 0 and 100."
+        );
+        assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_ARGUMENT");
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn invalid_cursor() -> Result<()> {
-    let mut router = app().await?;
-    let app = router.ready().await?;
-    create_users().await?;
+    let test_app = TestApp::new().await?;
+    create_users(&test_app).await?;
 
     let args = ReadUsersArguments {
         first: Some(1),
@@ -92,50 +98,33 @@ async fn invalid_cursor() -> Result<()> {
         before: None,
     };
     let query = UsersQuery::build(args);
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let body: Value = from_slice(&resp_byte)?;
+    let body = test_app.graphql(&query, None).await?;
     let error_message = &body["errors"][0]["message"];
     assert_eq!(error_message, "Invalid cursor");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "INVALID_ARGUMENT");
     Ok(())
 }
 
-async fn create_users() -> Result<()> {
-    let mut router = app().await?;
-    let app = router.ready().await?;
+/// The first user is an admin, creating the others
+pub(super) async fn create_users(test_app: &TestApp) -> Result<()> {
+    let access_token = admin(test_app, "one").await?;
 
-    let names = ["one", "two", "three", "four", "five", "six"];
+    let names = ["two", "three", "four", "five", "six"];
     for name in names {
         let args = add::CreateUserInput {
             name: name.to_string(),
             full_name: None,
         };
         let query = add::UserMutation::build(&args);
-
-        let request = Request::builder()
-            .method(http::Method::POST)
-            .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-            .uri("/graphql")
-            .body(Body::from(to_string(&query)?))?;
-
-        let response = app.call(request).await?;
-        assert_eq!(response.status(), StatusCode::OK);
+        test_app.graphql(&query, Some(&access_token)).await?;
     }
     Ok(())
 }
 
 #[tokio::test]
 async fn find_paginated_user() -> Result<()> {
-    let mut router = app().await?;
-    let app = router.ready().await?;
-    create_users().await?;
+    let test_app = TestApp::new().await?;
+    create_users(&test_app).await?;
 
     let args = ReadUsersArguments {
         first: Some(1),
@@ -144,16 +133,8 @@ async fn find_paginated_user() -> Result<()> {
         before: None,
     };
     let query = UsersQuery::build(args);
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let users_response: UsersResponse = from_slice(&resp_byte)?;
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
     assert_eq!(users_response.data.users.total_count, 6);
     //
     // first edges
@@ -172,16 +153,8 @@ async fn find_paginated_user() -> Result<()> {
         before: None,
     };
     let query = UsersQuery::build(args);
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
-
-    let response = app.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
-
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let users_response: UsersResponse = from_slice(&resp_byte)?;
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
     assert_eq!(users_response.data.users.edges[0].node.name, "two");
 
     let two_cursor = users_response.data.users.edges[0].cursor.clone();
@@ -195,18 +168,87 @@ async fn find_paginated_user() -> Result<()> {
         before: Some(two_cursor),
     };
     let query = UsersQuery::build(args);
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
+    assert_eq!(users_response.data.users.edges[0].node.name, "one");
 
-    let response = app.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
+    Ok(())
+}
 
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let users_response: UsersResponse = from_slice(&resp_byte)?;
-    assert_eq!(users_response.data.users.edges[0].node.name, "one");
+#[tokio::test]
+async fn relay_spec_conformance() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    create_users(&test_app).await?;
+
+    let all = read_users(&test_app, Some(6), None, None, None).await?;
+    let cursor = |index: usize| Some(all.edges[index].cursor.clone());
+    assert!(!all.page_info.has_previous_page);
+    assert!(!all.page_info.has_next_page);
+
+    //
+    // first & after
+    //
+    let users = read_users(&test_app, Some(2), cursor(0), None, None).await?;
+    assert_eq!(names(&users), ["two", "three"]);
+    assert!(users.page_info.has_previous_page);
+    assert!(users.page_info.has_next_page);
+    //
+    // first & before
+    //
+    let users = read_users(&test_app, Some(2), None, None, cursor(3)).await?;
+    assert_eq!(names(&users), ["one", "two"]);
+    assert!(!users.page_info.has_previous_page);
+    assert!(users.page_info.has_next_page);
+    //
+    // last & after
+    //
+    let users = read_users(&test_app, None, cursor(2), Some(2), None).await?;
+    assert_eq!(names(&users), ["five", "six"]);
+    assert!(users.page_info.has_previous_page);
+    assert!(!users.page_info.has_next_page);
+    //
+    // last & before
+    //
+    let users = read_users(&test_app, None, None, Some(5), cursor(2)).await?;
+    assert_eq!(names(&users), ["one", "two"]);
+    assert!(!users.page_info.has_previous_page);
+    assert!(users.page_info.has_next_page);
+    //
+    // after & before
+    //
+    let users = read_users(&test_app, Some(10), cursor(0), None, cursor(3)).await?;
+    assert_eq!(names(&users), ["two", "three"]);
+    assert!(users.page_info.has_previous_page);
+    assert!(users.page_info.has_next_page);
+    assert_eq!(users.page_info.start_cursor, cursor(1));
+    assert_eq!(users.page_info.end_cursor, cursor(2));
 
-    teardown().await?;
     Ok(())
 }
+
+async fn read_users(
+    test_app: &TestApp,
+    first: Option<i32>,
+    after: Option<String>,
+    last: Option<i32>,
+    before: Option<String>,
+) -> Result<UserConnection> {
+    let args = ReadUsersArguments {
+        first,
+        after,
+        last,
+        before,
+    };
+    let query = UsersQuery::build(args);
+    let body = test_app.graphql(&query, None).await?;
+    let users_response: UsersResponse = from_value(body)?;
+    Ok(users_response.data.users)
+}
+
+fn names(users: &UserConnection) -> Vec<&str> {
+    users
+        .edges
+        .iter()
+        .map(|edge| edge.node.name.as_str())
+        .collect()
+}
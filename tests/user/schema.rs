@@ -20,6 +20,15 @@ pub struct UserConnectionWrapper {
 pub struct UserConnection {
     pub total_count: i32,
     pub edges: Vec<UserEdge>,
+    pub page_info: PageInfo,
+}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
 }
 #[derive(Debug, Deserialize)]
 #[serde(rename_all(deserialize = "camelCase"))]
@@ -82,7 +91,8 @@ pub struct DeleteUserWrapper {
 // To match GraphQL response field camelCase,
 // we use `serde::rename_all` to change our Rust struct field into camelCae.
 pub struct User {
-    pub id: Uuid,
+    pub id: String,
+    pub uuid: Uuid,
     pub name: String,
     pub full_name: Option<String>,
 }
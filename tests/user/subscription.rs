@@ -0,0 +1,93 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use async_graphql::Request;
+use graph::{
+    config::{Config, EventsBackend},
+    db,
+    routes::AppBuilder,
+    schema::AppSchema,
+};
+use tokio_stream::StreamExt;
+
+use crate::{auth::admin_principal, harness::TestApp};
+
+/// A pool and an event bus of its own, like another instance of the app
+async fn instance(config: Config) -> Result<AppSchema> {
+    let db = db::connect(&config.database).await?;
+    Ok(AppBuilder::new(Arc::new(config), db).build_schema().await?)
+}
+
+#[tokio::test]
+async fn user_created() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let schema = AppBuilder::new(Arc::clone(&test_app.config), test_app.db.clone())
+        .build_schema()
+        .await?;
+
+    let subscription_schema = schema.clone();
+    let next_event = tokio::spawn(async move {
+        let request = Request::new("subscription { userCreated { name } }");
+        let mut stream =
+            Box::pin(subscription_schema.execute_stream(request.data(admin_principal())));
+        stream.next().await
+    });
+    // Give the subscription time to start listening
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let request = Request::new(r#"mutation { createUser(input: { name: "khawa" }) { id } }"#);
+    let response = schema.execute(request.data(admin_principal())).await;
+    assert!(response.errors.is_empty());
+
+    let event = tokio::time::timeout(Duration::from_secs(5), next_event)
+        .await??
+        .expect("user created event");
+    let data = event.data.into_json()?;
+    assert_eq!(data["userCreated"]["name"], "khawa");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn user_created_on_other_instance() -> Result<()> {
+    let test_app = TestApp::new().await?;
+
+    let mut config = Config::clone(&test_app.config);
+    config.events.backend = EventsBackend::Postgres;
+    let one = instance(config.clone()).await?;
+    let two = instance(config).await?;
+
+    let next_event = tokio::spawn(async move {
+        let request = Request::new("subscription { userCreated { name } }");
+        let mut stream = Box::pin(two.execute_stream(request.data(admin_principal())));
+        stream.next().await
+    });
+    // Give the subscription time to start listening
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let request = Request::new(r#"mutation { createUser(input: { name: "khawa" }) { id } }"#);
+    let response = one.execute(request.data(admin_principal())).await;
+    assert!(response.errors.is_empty());
+
+    let event = tokio::time::timeout(Duration::from_secs(5), next_event)
+        .await??
+        .expect("user created event");
+    let data = event.data.into_json()?;
+    assert_eq!(data["userCreated"]["name"], "khawa");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn user_created_requires_admin() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let schema = AppBuilder::new(Arc::clone(&test_app.config), test_app.db.clone())
+        .build_schema()
+        .await?;
+
+    let mut stream = Box::pin(schema.execute_stream("subscription { userCreated { name } }"));
+    let response = stream.next().await.expect("error response");
+    assert_eq!(response.errors[0].message, "authentication required");
+
+    Ok(())
+}
@@ -1,71 +1,78 @@
 use anyhow::Result;
-use axum::{
-    body::Body,
-    http::{self, Request, StatusCode},
-};
 use cynic::MutationBuilder;
-use graph::routes::app;
-use serde_json::{from_slice, to_string};
-use tower::{util::ServiceExt, Service};
+use serde_json::{from_value, Value};
 
-use super::{
-    graphql::{add, update},
-    schema::{CreateUserResponse, UpdateUserResponse},
+use super::{graphql::update, schema::UpdateUserResponse};
+use crate::{
+    auth::{admin, register},
+    harness::TestApp,
 };
-use crate::user::{graphql::update::Uuid, teardown};
 
 #[tokio::test]
 async fn update_user() -> Result<()> {
-    let mut app = app().await?;
+    let test_app = TestApp::new().await?;
     //
     // Create User
     //
 
-    let args = add::CreateUserInput {
-        name: "khawa".to_string(),
-        full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
-    };
-    let query = add::UserMutation::build(&args);
+    let user = register(&test_app, "khawa", Some("Abu Musa Al-Khawarizmi")).await?;
 
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
+    //
+    // Update User
+    //
+    let args = update::UpdateUserInput {
+        id: Some(update::Uuid(user.uuid.to_string())),
+        name: Some("haitham".to_string()),
+        full_name: None,
+    };
+    let query = update::UserMutation::build(&args);
 
-    let response = app.ready().await?.call(request).await?;
-    assert_eq!(response.status(), StatusCode::OK);
+    let body = test_app.graphql(&query, Some(&user.access_token)).await?;
+    let user_response: UpdateUserResponse = from_value(body)?;
 
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let user_response: CreateUserResponse = from_slice(&resp_byte)?;
-    assert_eq!(user_response.data.create_user.name, "khawa");
+    assert_eq!(user_response.data.update_user.name, "haitham");
 
-    let user_id = user_response.data.create_user.id;
+    Ok(())
+}
 
-    //
-    // Update User
-    //
-    let user_id = Uuid(user_id.to_string());
+#[tokio::test]
+async fn update_user_same_name() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let user = register(&test_app, "khawa", None).await?;
 
     let args = update::UpdateUserInput {
-        id: user_id,
-        name: "haitham".to_string(),
-        full_name: None,
+        id: Some(update::Uuid(user.uuid.to_string())),
+        name: Some("khawa".to_string()),
+        full_name: Some("Abu Musa Al-Khawarizmi".to_string()),
     };
     let query = update::UserMutation::build(&args);
+    let body = test_app.graphql(&query, Some(&user.access_token)).await?;
 
-    let request = Request::builder()
-        .method(http::Method::POST)
-        .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
-        .uri("/graphql")
-        .body(Body::from(to_string(&query)?))?;
+    assert_eq!(body["errors"], Value::Null);
+    assert_eq!(body["data"]["updateUser"]["name"], "khawa");
+    assert_eq!(
+        body["data"]["updateUser"]["fullName"],
+        "Abu Musa Al-Khawarizmi"
+    );
 
-    let response = app.ready().await?.call(request).await?;
-    let resp_byte = hyper::body::to_bytes(response.into_body()).await?;
-    let user_response: UpdateUserResponse = from_slice(&resp_byte)?;
+    Ok(())
+}
 
-    assert_eq!(user_response.data.update_user.name, "haitham");
+#[tokio::test]
+async fn update_unknown_user() -> Result<()> {
+    let test_app = TestApp::new().await?;
+    let access_token = admin(&test_app, "haitham").await?;
+
+    let args = update::UpdateUserInput {
+        id: Some(update::Uuid(uuid::Uuid::nil().to_string())),
+        name: Some("khawa".to_string()),
+        full_name: None,
+    };
+    let query = update::UserMutation::build(&args);
+    let body = test_app.graphql(&query, Some(&access_token)).await?;
+
+    assert_eq!(body["errors"][0]["message"], "user not found");
+    assert_eq!(body["errors"][0]["extensions"]["code"], "NOT_FOUND");
 
-    teardown().await?;
     Ok(())
 }